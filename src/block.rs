@@ -1,8 +1,8 @@
 use ffi::core;
-use ffi::prelude::LLVMBasicBlockRef;
+use ffi::prelude::{LLVMBasicBlockRef, LLVMValueRef};
 use std::iter::{Iterator, DoubleEndedIterator, IntoIterator};
 use std::marker::PhantomData;
-use std::mem;
+use std::{mem, ptr};
 use std::ops::Deref;
 use value::{Function, Value};
 use util::{self, Sub};
@@ -31,7 +31,8 @@ impl BasicBlock {
     pub fn get_parent(&self) -> Option<&Function> {
         unsafe { util::ptr_to_null(core::LLVMGetBasicBlockParent(self.into())) }
     }
-    /// Return the terminator instruction for this basic block.
+    /// Return the terminator instruction for this basic block, or `None` if it
+    /// hasn't been terminated yet.
     pub fn get_terminator(&self) -> Option<&Value> {
         unsafe { util::ptr_to_null(core::LLVMGetBasicBlockTerminator(self.into())) }
     }
@@ -43,6 +44,29 @@ impl BasicBlock {
     pub fn get_last(&self) -> Option<&Value> {
         unsafe { util::ptr_to_null(core::LLVMGetLastInstruction(self.into())) }
     }
+    /// Returns the blocks that this block's terminator can branch to.
+    ///
+    /// This will be empty if the block hasn't been terminated yet.
+    pub fn successors(&self) -> Vec<&BasicBlock> {
+        match self.get_terminator() {
+            Some(term) => unsafe {
+                let count = core::LLVMGetNumSuccessors(term.into());
+                (0..count).map(|index| core::LLVMGetSuccessor(term.into(), index).into()).collect()
+            },
+            None => Vec::new()
+        }
+    }
+    /// Iterate through the instructions in this basic block, in order.
+    pub fn instructions(&self) -> Instructions {
+        Instructions {
+            value: unsafe { core::LLVMGetFirstInstruction(self.into()) },
+            marker: PhantomData
+        }
+    }
+    /// Returns the first instruction in this basic block with the name given, or `None` if there is none.
+    pub fn find_instruction(&self, name: &str) -> Option<&Value> {
+        self.instructions().find(|instr| instr.get_name() == Some(name))
+    }
     /// Move this basic block after the `other` basic block in its function.
     pub fn move_after(&self, other: &BasicBlock) {
         unsafe { core::LLVMMoveBasicBlockAfter(self.into(), other.into()) }
@@ -64,18 +88,48 @@ impl BasicBlock {
     }
 }
 
+#[derive(Copy, Clone)]
+/// An iterator through the instructions contained in a basic block.
+pub struct Instructions<'a> {
+    value: LLVMValueRef,
+    marker: PhantomData<&'a ()>
+}
+impl<'a> Iterator for Instructions<'a> {
+    type Item = &'a Value;
+    fn next(&mut self) -> Option<&'a Value> {
+        if self.value.is_null() {
+            None
+        } else {
+            let instr = self.value;
+            self.value = unsafe { core::LLVMGetNextInstruction(instr) };
+            Some(instr.into())
+        }
+    }
+}
+
 /// Iterates through all the blocks contained in a function.
+#[derive(Copy, Clone)]
 pub struct BlockIter<'a> {
-    pub min: &'a BasicBlock,
-    pub max: &'a BasicBlock
+    min: LLVMBasicBlockRef,
+    max: LLVMBasicBlockRef,
+    marker: PhantomData<&'a ()>
 }
 impl<'a> BlockIter<'a> {
     pub fn new(function: &'a Function) -> BlockIter<'a> {
         BlockIter {
-            min: unsafe { core::LLVMGetFirstBasicBlock(function.into()).into() },
-            max: unsafe { core::LLVMGetLastBasicBlock(function.into()).into() }
+            min: unsafe { core::LLVMGetFirstBasicBlock(function.into()) },
+            max: unsafe { core::LLVMGetLastBasicBlock(function.into()) },
+            marker: PhantomData
         }
     }
+    /// Returns the block this will yield next from the front, or `None` if it is exhausted.
+    pub fn get_min(&self) -> Option<&'a BasicBlock> {
+        unsafe { util::ptr_to_null(self.min) }
+    }
+    /// Returns the block this will yield next from the back, or `None` if it is exhausted.
+    pub fn get_max(&self) -> Option<&'a BasicBlock> {
+        unsafe { util::ptr_to_null(self.max) }
+    }
 }
 
 impl<'a> IntoIterator for &'a Function {
@@ -88,27 +142,33 @@ impl<'a> IntoIterator for &'a Function {
 impl<'a> Iterator for BlockIter<'a> {
     type Item = &'a BasicBlock;
     fn next(&mut self) -> Option<&'a BasicBlock> {
-        if self.min == self.max {
+        if self.min.is_null() {
             None
         } else {
-            unsafe {
-                let _block = self.min;
-                self.min = core::LLVMGetNextBasicBlock(self.min.into()).into();
-                Some(_block)
+            let block = self.min;
+            if block == self.max {
+                self.min = ptr::null_mut();
+                self.max = ptr::null_mut();
+            } else {
+                self.min = unsafe { core::LLVMGetNextBasicBlock(block) };
             }
+            Some(block.into())
         }
     }
 }
 impl<'a> DoubleEndedIterator for BlockIter<'a> {
     fn next_back(&mut self) -> Option<&'a BasicBlock> {
-        if self.min == self.max {
+        if self.max.is_null() {
             None
         } else {
-            unsafe {
-                let _block = self.max;
-                self.max = core::LLVMGetPreviousBasicBlock(self.max.into()).into();
-                Some(_block)
+            let block = self.max;
+            if block == self.min {
+                self.min = ptr::null_mut();
+                self.max = ptr::null_mut();
+            } else {
+                self.max = unsafe { core::LLVMGetPreviousBasicBlock(block) };
             }
+            Some(block.into())
         }
     }
-}
\ No newline at end of file
+}
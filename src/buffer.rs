@@ -1,16 +1,25 @@
-use libc::c_char;
+use libc::{c_char, size_t};
 use ffi::{core, LLVMMemoryBuffer};
 use ffi::prelude::LLVMMemoryBufferRef;
 use cbox::{CBox, DisposeRef};
 use std::ops::Deref;
 use std::marker::PhantomData;
-use std::mem;
+use std::{mem, slice};
 use util;
 
-
+/// A block of memory containing the contents of a file or some data, such as bitcode.
 pub struct MemoryBuffer(PhantomData<[u8]>);
 native_ref!(&MemoryBuffer = LLVMMemoryBufferRef);
 impl MemoryBuffer {
+    /// Create a new buffer containing a copy of the data given.
+    ///
+    /// The name is used to identify the buffer in error messages.
+    pub fn new_from_slice(data: &[u8], name: &str) -> CBox<MemoryBuffer> {
+        util::with_cstr(name, |name| unsafe {
+            CBox::new(core::LLVMCreateMemoryBufferWithMemoryRangeCopy(data.as_ptr() as *const c_char, data.len() as size_t, name))
+        })
+    }
+    /// Create a new buffer containing the contents of the file at the path given.
     pub fn new_from_file(path: &str) -> Result<CBox<MemoryBuffer>, CBox<str>> {
         util::with_cstr(path, |path| unsafe {
             let mut output = mem::MaybeUninit::uninit();
@@ -23,6 +32,18 @@ impl MemoryBuffer {
         })
     }
 }
+impl MemoryBuffer {
+    /// Returns the contents of this buffer.
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe {
+            slice::from_raw_parts(core::LLVMGetBufferStart(self.into()) as *const u8, self.get_size())
+        }
+    }
+    /// Returns the size of this buffer in bytes.
+    pub fn get_size(&self) -> usize {
+        unsafe { core::LLVMGetBufferSize(self.into()) as usize }
+    }
+}
 impl Deref for MemoryBuffer {
     type Target = str;
     fn deref(&self) -> &str {
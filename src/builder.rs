@@ -1,16 +1,14 @@
-use libc::{c_char, c_uint};
-use ffi::prelude::{LLVMBasicBlockRef, LLVMBuilderRef, LLVMValueRef};
+use libc::{c_char, c_int, c_uint};
+use ffi::prelude::{LLVMBuilderRef, LLVMValueRef};
 use ffi::{core, LLVMBuilder, LLVMRealPredicate, LLVMIntPredicate};
 use cbox::CSemiBox;
 use std::marker::PhantomData;
-use std::mem;
 use block::BasicBlock;
-use context::Context;
-use types::Type;
-use value::{Function, Value, Predicate};
-
-static NULL_NAME:[c_char; 1] = [0];
-
+use context::{Context, GetContext};
+use module::Module;
+use types::{ArrayType, FunctionType, IntegerType, PointerType, StructType, Type, VectorType};
+use util::{self, Sub, NULL_NAME};
+use value::{AtomicOrdering, AtomicRMWBinOp, Function, IntPredicate, LandingPad, PhiNode, RealPredicate, Switch, Value, ValueKind, Predicate};
 
 /// This provides a uniform API for creating instructions and inserting them into a basic block.
 pub struct Builder(PhantomData<[u8]>);
@@ -22,6 +20,20 @@ macro_rules! bin_op(
             unsafe { core::$func(self.into(), left.into(), right.into(), NULL_NAME.as_ptr()) }.into()
         }
     );
+    (int $name:ident, $func:ident) => (
+        pub fn $name(&self, left: &Value, right: &Value) -> &Value {
+            let ty = left.get_type();
+            assert!(is_integer(ty) && ty == right.get_type(), "expected integer operands, got {:?} and {:?}", ty, right.get_type());
+            unsafe { core::$func(self.into(), left.into(), right.into(), NULL_NAME.as_ptr()) }.into()
+        }
+    );
+    (float $name:ident, $func:ident) => (
+        pub fn $name(&self, left: &Value, right: &Value) -> &Value {
+            let ty = left.get_type();
+            assert!(is_floating_point(ty) && ty == right.get_type(), "expected floating-point operands, got {:?} and {:?}", ty, right.get_type());
+            unsafe { core::$func(self.into(), left.into(), right.into(), NULL_NAME.as_ptr()) }.into()
+        }
+    );
     ($name:ident, $ifunc:ident, $ffunc:ident) => (
         pub fn $name(&self, left: &Value, right: &Value) -> &Value {
             let ty = left.get_type();
@@ -42,8 +54,62 @@ macro_rules! un_op(
         }
     );
 );
+fn is_integer(ty: &Type) -> bool {
+    match VectorType::from_super(ty) {
+        Some(vec) => vec.get_element().is_integer(),
+        None => ty.is_integer()
+    }
+}
+fn is_floating_point(ty: &Type) -> bool {
+    match VectorType::from_super(ty) {
+        Some(vec) => vec.get_element().is_floating_point(),
+        None => ty.is_floating_point()
+    }
+}
+fn is_bool(ty: &Type) -> bool {
+    IntegerType::from_super(ty).map(|ty| ty.get_width()) == Some(1)
+}
+fn check_widths<F>(from: &Type, to: &Type, valid: F, expected: &str) where F: FnOnce(usize, usize) -> bool {
+    if let (Some(from_ty), Some(to_ty)) = (IntegerType::from_super(from), IntegerType::from_super(to)) {
+        if !valid(from_ty.get_width(), to_ty.get_width()) {
+            panic!("expected a type {} than {:?}, got {:?}", expected, from, to)
+        }
+    }
+}
+fn get_field_type(agg: &Type, index: usize) -> &Type {
+    let (field, fields) = if let Some(struct_ty) = StructType::from_super(agg) {
+        let elements = struct_ty.get_elements();
+        (elements.get(index).map(|&ty| ty), elements.len())
+    } else if let Some(array_ty) = ArrayType::from_super(agg) {
+        (Some(array_ty.get_element()), array_ty.get_length())
+    } else {
+        panic!("expected struct or array, got {:?}", agg)
+    };
+    match field {
+        Some(field) if index < fields => field,
+        _ => panic!("no such field {} on {:?}", index, agg)
+    }
+}
+fn get_vector_type(ty: &Type) -> &VectorType {
+    match VectorType::from_super(ty) {
+        Some(vec) => vec,
+        None => panic!("expected vector, got {:?}", ty)
+    }
+}
+fn check_size(size: &Value) {
+    assert!(size.get_type().is_integer(), "expected integer size, got {:?}", size.get_type())
+}
+fn check_call_args(func: &Function, args: &[&Value]) {
+    let sig = func.get_signature();
+    let (expected, given) = (sig.num_params(), args.len());
+    if given != expected && !(sig.is_var_arg() && given > expected) {
+        panic!("expected {} arguments to {:?}, got {}", expected, func.get_name(), given)
+    }
+}
 impl Builder {
     /// Create a new builder in the context given.
+    ///
+    /// The builder is disposed when the returned box is dropped, and it can't outlive the context.
     pub fn new(context: &Context) -> CSemiBox<Builder> {
         CSemiBox::new(unsafe { core::LLVMCreateBuilderInContext(context.into()) }.into())
     }
@@ -51,29 +117,52 @@ impl Builder {
     pub fn position_at_end(&self, block: &BasicBlock) {
         unsafe { core::LLVMPositionBuilderAtEnd(self.into(), block.into()) }
     }
-    /// Build a PHI instruction with no useful values
-    pub fn build_phi(&self, incoming: Vec<(&BasicBlock, &Value)>) -> &Value{
-        let count = incoming.len() as u32;
-        assert!(count > 0);
-        let ty = incoming[0].1.get_type();
-
-        let mut incoming_values: Vec<LLVMValueRef> = Vec::with_capacity(incoming.len());
-        let mut incoming_blocks: Vec<LLVMBasicBlockRef> = Vec::with_capacity(incoming.len());
-        for (bb, v) in incoming {
-            incoming_values.push(v.into());
-            incoming_blocks.push(bb.into());
-        }
-        unsafe {
-            let phi = core::LLVMBuildPhi(self.into(), ty.into(), NULL_NAME.as_ptr());
-            core::LLVMAddIncoming(phi, incoming_values.as_mut_ptr(), incoming_blocks.as_mut_ptr(), count);
-            phi
-        }.into()
+    /// Position the builder immediately before the instruction `instr`.
+    pub fn position_before(&self, instr: &Value) {
+        unsafe { core::LLVMPositionBuilderBefore(self.into(), instr.into()) }
+    }
+    /// Returns the block the builder is inserting instructions into, or `None` if it
+    /// hasn't been positioned yet.
+    pub fn get_insert_block(&self) -> Option<&BasicBlock> {
+        unsafe { util::ptr_to_null(core::LLVMGetInsertBlock(self.into())) }
+    }
+    /// Set the debug location that will be attached to the instructions built after this.
+    ///
+    /// The location must be a `DILocation` metadata node, such as the `dbg` metadata
+    /// of another instruction. This panics if `loc` isn't metadata, but the C API has no
+    /// way to check that the node is a `DILocation` specifically.
+    ///
+    /// The C API this binds has no `DIBuilder`, so a location can't be created from a
+    /// line and column, and there is no `LLVMGetDebugLocLine` to read them back either.
+    pub fn set_current_debug_location(&self, loc: &Value) {
+        assert!(loc.get_kind() == ValueKind::MetadataAsValue, "expected a debug location metadata node, got {:?}", loc);
+        unsafe { core::LLVMSetCurrentDebugLocation(self.into(), loc.into()) }
+    }
+    /// Returns the debug location that will be attached to the instructions built after
+    /// this, or `None` if it hasn't been set.
+    pub fn get_current_debug_location(&self) -> Option<&Value> {
+        unsafe { util::ptr_to_null(core::LLVMGetCurrentDebugLocation(self.into())) }
+    }
+    /// Attach the current debug location to the instruction given.
+    pub fn set_inst_debug_location(&self, instr: &Value) {
+        unsafe { core::LLVMSetInstDebugLocation(self.into(), instr.into()) }
+    }
+    /// Build a PHI node of the type given with no incoming values.
+    ///
+    /// Incoming values can be added to it with `PhiNode::add_incoming`.
+    pub fn build_phi(&self, ty: &Type) -> &PhiNode {
+        unsafe { core::LLVMBuildPhi(self.into(), ty.into(), NULL_NAME.as_ptr()) }.into()
     }
     /// Build an instruction that returns from the function with void.
+    ///
+    /// Like every terminator, this should be the last instruction built into its block.
     pub fn build_ret_void(&self) -> &Value {
         unsafe { core::LLVMBuildRetVoid(self.into()) }.into()
     }
     /// Build an instruction that returns from the function with `value`.
+    ///
+    /// Like every terminator, this should be the last instruction built into its block, so
+    /// make sure the builder has been moved to another block before building anything else.
     pub fn build_ret(&self, value: &Value) -> &Value {
         unsafe { core::LLVMBuildRet(self.into(), value.into()) }.into()
     }
@@ -97,22 +186,150 @@ impl Builder {
     pub fn build_unreachable(&self) -> &Value {
         unsafe { core::LLVMBuildUnreachable(self.into()).into() }
     }
+    /// Build an instruction that loads the value pointed to by `ptr`.
+    ///
+    /// The loaded value will have the element type of the pointer.
+    pub fn build_load(&self, ptr: &Value) -> &Value {
+        let ty = ptr.get_type();
+        assert!(ty.is_pointer(), "expected pointer to load from, got {:?}", ty);
+        unsafe { core::LLVMBuildLoad(self.into(), ptr.into(), NULL_NAME.as_ptr()) }.into()
+    }
     /// Build an instruction that store the value `val` in the pointer `ptr`.
     pub fn build_store(&self, val: &Value, ptr: &Value) -> &Value {
         unsafe { core::LLVMBuildStore(self.into(), val.into(), ptr.into()) }.into()
     }
+    /// Build an instruction that atomically applies `op` to the value pointed to by `ptr`
+    /// and `val`, stores the result in `ptr` and yields the old value.
+    ///
+    /// If `single_thread` is true, it is only atomic with respect to code running in the same thread.
+    pub fn build_atomic_rmw(&self, op: AtomicRMWBinOp, ptr: &Value, val: &Value, ordering: AtomicOrdering, single_thread: bool) -> &Value {
+        let ty = ptr.get_type();
+        match PointerType::from_super(ty) {
+            Some(ptr_ty) => assert!(ptr_ty.get_element() == val.get_type(), "cannot apply {:?} to {:?} and {:?}", op, ty, val.get_type()),
+            None => panic!("expected pointer, got {:?}", ty)
+        }
+        assert!(ordering != AtomicOrdering::NotAtomic && ordering != AtomicOrdering::Unordered, "{:?} is not a valid ordering for atomicrmw", ordering);
+        unsafe { core::LLVMBuildAtomicRMW(self.into(), op.into(), ptr.into(), val.into(), ordering.into(), single_thread as c_int) }.into()
+    }
+    /// Build a fence, which orders the memory operations before it with the ones after it.
+    ///
+    /// If `single_thread` is true, it only synchronises with code running in the same thread.
+    pub fn build_fence(&self, ordering: AtomicOrdering, single_thread: bool) -> &Value {
+        match ordering {
+            AtomicOrdering::Acquire | AtomicOrdering::Release | AtomicOrdering::AcqRel | AtomicOrdering::SeqCst => (),
+            _ => panic!("{:?} is not a valid ordering for a fence", ordering)
+        }
+        unsafe { core::LLVMBuildFence(self.into(), ordering.into(), single_thread as c_int, NULL_NAME.as_ptr()) }.into()
+    }
+    /// Build an instruction that reads the next variable argument of the type given from
+    /// the `va_list` pointed to by `list`, then advances the list.
+    ///
+    /// The list must be started with a call to `llvm.va_start` first.
+    pub fn build_va_arg(&self, list: &Value, ty: &Type) -> &Value {
+        assert!(list.get_type().is_pointer(), "expected pointer to va_list, got {:?}", list.get_type());
+        unsafe { core::LLVMBuildVAArg(self.into(), list.into(), ty.into(), NULL_NAME.as_ptr()).into() }
+    }
+    /// Build a call to `llvm.memcpy`, which copies `size` bytes from `src` to `dst`.
+    ///
+    /// Both pointers must be aligned to at least `align` bytes, and the memory they
+    /// point to must not overlap. Since the LLVM this binds takes the alignment as an
+    /// argument to the intrinsic, there is a single `align` for both pointers.
+    ///
+    /// This panics if the builder isn't positioned in a function, since the intrinsic is
+    /// declared in that function's module.
+    pub fn build_memcpy(&self, dst: &Value, src: &Value, size: &Value, align: u32) -> &Value {
+        self.build_mem_transfer("llvm.memcpy", dst, src, size, align)
+    }
+    /// Build a call to `llvm.memmove`, which copies `size` bytes from `src` to `dst`, even
+    /// if the memory they point to overlaps.
+    ///
+    /// Both pointers must be aligned to at least `align` bytes. Since the LLVM this binds
+    /// takes the alignment as an argument to the intrinsic, there is a single `align` for
+    /// both pointers.
+    ///
+    /// This panics if the builder isn't positioned in a function, since the intrinsic is
+    /// declared in that function's module.
+    pub fn build_memmove(&self, dst: &Value, src: &Value, size: &Value, align: u32) -> &Value {
+        self.build_mem_transfer("llvm.memmove", dst, src, size, align)
+    }
+    /// Build a call to `llvm.memset`, which sets `size` bytes starting at `ptr` to the `i8` value `val`.
+    ///
+    /// The pointer must be aligned to at least `align` bytes, which is passed as an
+    /// argument to the intrinsic in the LLVM this binds.
+    ///
+    /// This panics if the builder isn't positioned in a function, since the intrinsic is
+    /// declared in that function's module.
+    pub fn build_memset(&self, ptr: &Value, val: &Value, size: &Value, align: u32) -> &Value {
+        let ctx = ptr.get_context();
+        let i8_t = Type::get_i8(ctx);
+        assert!(val.get_type() == i8_t, "expected i8 value, got {:?}", val.get_type());
+        check_size(size);
+        let sig = FunctionType::new(Type::get_void(ctx), &[i8_t.pointer_to(), i8_t, size.get_type(), Type::get_i32(ctx), Type::get_i1(ctx)], false);
+        let memset = self.get_module().get_intrinsic_declaration("llvm.memset", &[i8_t.pointer_to(), size.get_type()], sig).unwrap();
+        let ptr = self.build_byte_ptr(ptr);
+        self.build_call(memset, &[ptr, val, size, Value::new_int(Type::get_i32(ctx), align as u64, false), Value::new_bool(ctx, false)])
+    }
+    fn build_mem_transfer(&self, name: &str, dst: &Value, src: &Value, size: &Value, align: u32) -> &Value {
+        let ctx = dst.get_context();
+        check_size(size);
+        let byte_ptr = Type::get_i8(ctx).pointer_to();
+        let sig = FunctionType::new(Type::get_void(ctx), &[byte_ptr, byte_ptr, size.get_type(), Type::get_i32(ctx), Type::get_i1(ctx)], false);
+        let func = self.get_module().get_intrinsic_declaration(name, &[byte_ptr, byte_ptr, size.get_type()], sig).unwrap();
+        let (dst, src) = (self.build_byte_ptr(dst), self.build_byte_ptr(src));
+        self.build_call(func, &[dst, src, size, Value::new_int(Type::get_i32(ctx), align as u64, false), Value::new_bool(ctx, false)])
+    }
+    /// Returns the module the function the builder is positioned in belongs to.
+    fn get_module(&self) -> &Module {
+        let block = self.get_insert_block().expect("the builder must be positioned in a block to call an intrinsic");
+        let func = block.get_parent().expect("the builder must be positioned in a function to call an intrinsic");
+        unsafe { core::LLVMGetGlobalParent(func.into()).into() }
+    }
+    /// Cast the pointer given to an `i8*` if it isn't one already.
+    fn build_byte_ptr(&self, ptr: &Value) -> &Value {
+        let ty = ptr.get_type();
+        match PointerType::from_super(ty) {
+            Some(ptr_ty) => assert!(ptr_ty.get_address_space() == 0, "expected pointer in the generic address space, got {:?}", ty),
+            None => panic!("expected pointer, got {:?}", ty)
+        }
+        let byte_ptr = Type::get_i8(ptr.get_context()).pointer_to();
+        if ty == byte_ptr {
+            ptr
+        } else {
+            self.build_bit_cast(ptr, byte_ptr)
+        }
+    }
+    /// Build a private global constant containing `text` as a C string, then return a
+    /// pointer to its first character.
+    ///
+    /// The global will be given the name `name`. Since C strings can't contain null
+    /// bytes, this panics if there are any in `text`.
+    ///
+    /// The builder must be positioned inside a function for this to work.
+    pub fn build_global_string_ptr(&self, text: &str, name: &str) -> &Value {
+        assert!(!text.contains('\0'), "string {:?} contains a null byte", text);
+        util::with_cstr(text, |c_text| util::with_cstr(name, |c_name| unsafe {
+            core::LLVMBuildGlobalStringPtr(self.into(), c_text, c_name).into()
+        }))
+    }
     /// Build an instruction that branches to the block `dest`.
     pub fn build_br(&self, dest: &BasicBlock) -> &Value {
         unsafe { core::LLVMBuildBr(self.into(), dest.into()).into() }
     }
     /// Build an instruction that branches to `if_block` if `cond` evaluates to true, and `else_block` otherwise.
-    pub fn build_cond_br(&self, cond: &Value, if_block: &BasicBlock, else_block: Option<&BasicBlock>) -> &Value {
-        unsafe { core::LLVMBuildCondBr(self.into(), cond.into(), if_block.into(), mem::transmute(else_block)).into() }
+    ///
+    /// The condition `cond` must be an `i1`.
+    pub fn build_cond_br(&self, cond: &Value, if_block: &BasicBlock, else_block: &BasicBlock) -> &Value {
+        let ty = cond.get_type();
+        assert!(is_bool(ty), "expected branch condition to be an i1, got {:?}", ty);
+        unsafe { core::LLVMBuildCondBr(self.into(), cond.into(), if_block.into(), else_block.into()).into() }
     }
     /// Build an instruction that calls the function `func` with the arguments `args`.
     ///
     /// This will return the return value of the function.
+    ///
+    /// The number of arguments must match the number of parameters `func` takes.
     pub fn build_call(&self, func: &Function, args: &[&Value]) -> &Value {
+        check_call_args(func, args);
         unsafe {
             let call = core::LLVMBuildCall(self.into(), func.into(), args.as_ptr() as *mut LLVMValueRef, args.len() as c_uint, NULL_NAME.as_ptr());
             core::LLVMSetTailCall(call, 0);
@@ -133,14 +350,49 @@ impl Builder {
     ///
     /// This will return the return value of the function.
     pub fn build_tail_call(&self, func: &Function, args: &[&Value]) -> &Value {
+        check_call_args(func, args);
         unsafe {
             let call = core::LLVMBuildCall(self.into(), func.into(), args.as_ptr() as *mut LLVMValueRef, args.len() as c_uint, NULL_NAME.as_ptr());
             core::LLVMSetTailCall(call, 1);
             call.into()
         }
     }
+    /// Build an instruction that calls the function `func` with the arguments `args`, then
+    /// branches to `then` if it returns normally or `catch` if it unwinds.
+    pub fn build_invoke(&self, func: &Function, args: &[&Value], then: &BasicBlock, catch: &BasicBlock) -> &Value {
+        check_call_args(func, args);
+        unsafe {
+            core::LLVMBuildInvoke(self.into(), func.into(), args.as_ptr() as *mut LLVMValueRef, args.len() as c_uint, then.into(), catch.into(), NULL_NAME.as_ptr()).into()
+        }
+    }
+    /// Build a landing pad of the type given with the clauses given, and make `personality`
+    /// the personality function of the function being built.
+    ///
+    /// More clauses can be added to it afterwards with `LandingPad::add_clause`.
+    pub fn build_landing_pad(&self, ty: &Type, personality: &Function, clauses: &[&Value]) -> &LandingPad {
+        let pad: &LandingPad = unsafe { core::LLVMBuildLandingPad(self.into(), ty.into(), personality.into(), clauses.len() as c_uint, NULL_NAME.as_ptr()).into() };
+        for clause in clauses {
+            pad.add_clause(clause);
+        }
+        pad
+    }
+    /// Build an instruction that resumes propagating the exception `exn`, which was caught by a landing pad.
+    pub fn build_resume(&self, exn: &Value) -> &Value {
+        unsafe { core::LLVMBuildResume(self.into(), exn.into()).into() }
+    }
     /// Build an instruction that yields to `true_val` if `cond` is equal to `1`, and `false_val` otherwise.
+    ///
+    /// The condition can either be an `i1`, or a vector of `i1` to select between
+    /// the elements of two vectors of the same length.
     pub fn build_select(&self, cond: &Value, true_val: &Value, false_val: &Value) -> &Value {
+        let (cond_ty, ty) = (cond.get_type(), true_val.get_type());
+        assert!(ty == false_val.get_type(), "expected both values to have type {:?}, got {:?}", ty, false_val.get_type());
+        let valid = match VectorType::from_super(cond_ty) {
+            Some(cond_vec) => is_bool(cond_vec.get_element()) &&
+                VectorType::from_super(ty).map(|vec| vec.get_size()) == Some(cond_vec.get_size()),
+            None => is_bool(cond_ty)
+        };
+        assert!(valid, "expected select condition to be an i1 or a matching vector of i1, got {:?}", cond_ty);
         unsafe { core::LLVMBuildSelect(self.into(), cond.into(), true_val.into(), false_val.into(), NULL_NAME.as_ptr()).into() }
     }
     /// Build an instruction that casts a value into a certain type.
@@ -151,18 +403,31 @@ impl Builder {
     pub fn build_int_to_ptr(&self, value: &Value, dest: &Type) -> &Value {
         unsafe { core::LLVMBuildIntToPtr(self.into(), value.into(), dest.into(), NULL_NAME.as_ptr()).into() }
     }
+    /// Build an instruction to convert a pointer into an integer.
+    pub fn build_ptr_to_int(&self, value: &Value, dest: &Type) -> &Value {
+        unsafe { core::LLVMBuildPtrToInt(self.into(), value.into(), dest.into(), NULL_NAME.as_ptr()).into() }
+    }
     /// Build an instruction that zero extends its operand to the type `dest`.
+    ///
+    /// When both types are integers, `dest` must be wider than the type of `value`.
     pub fn build_zext(&self, value: &Value, dest: &Type) -> &Value {
+        check_widths(value.get_type(), dest, |from, to| to > from, "wider");
         unsafe { core::LLVMBuildZExtOrBitCast(self.into(), value.into(), dest.into(), NULL_NAME.as_ptr()).into() }
     }
 
     /// Build an instruction that sign extends its operand to the type `dest`.
+    ///
+    /// When both types are integers, `dest` must be wider than the type of `value`.
     pub fn build_sext(&self, value: &Value, dest: &Type) -> &Value {
+        check_widths(value.get_type(), dest, |from, to| to > from, "wider");
         unsafe { core::LLVMBuildSExtOrBitCast(self.into(), value.into(), dest.into(), NULL_NAME.as_ptr()).into() }
     }
 
     /// Build an instruction that truncates the high-order bits of value to fit into a certain type.
+    ///
+    /// When both types are integers, `dest` must be narrower than the type of `value`.
     pub fn build_trunc(&self, value: &Value, dest: &Type) -> &Value {
+        check_widths(value.get_type(), dest, |from, to| to < from, "narrower");
         unsafe { core::LLVMBuildTrunc(self.into(), value.into(), dest.into(), NULL_NAME.as_ptr()).into() }
     }
     /// Build an instruction that converts a floating point value to an signed int type
@@ -192,47 +457,100 @@ impl Builder {
 
 
     /// Build an instruction that inserts a value into an aggregate data value.
+    ///
+    /// The value must have the same type as the field at `index` of the aggregate.
     pub fn build_insert_value(&self, agg: &Value, elem: &Value, index: usize) -> &Value {
+        let field = get_field_type(agg.get_type(), index);
+        assert!(field == elem.get_type(), "cannot insert {:?} into field {} of type {:?}", elem.get_type(), index, field);
         unsafe { core::LLVMBuildInsertValue(self.into(), agg.into(), elem.into(), index as c_uint, NULL_NAME.as_ptr()).into() }
     }
-    /// Build an instruction that extracts a value from an aggregate data value.
+    /// Build an instruction that extracts the field at `index` from an aggregate data value.
     pub fn build_extract_value(&self, agg: &Value, index: usize) -> &Value {
+        get_field_type(agg.get_type(), index);
         unsafe { core::LLVMBuildExtractValue(self.into(), agg.into(), index as c_uint, NULL_NAME.as_ptr()).into() }
     }
+    /// Build an instruction that extracts the element at `index` from the vector `vec`.
+    pub fn build_extract_element(&self, vec: &Value, index: &Value) -> &Value {
+        get_vector_type(vec.get_type());
+        assert!(index.get_type().is_integer(), "expected integer index, got {:?}", index.get_type());
+        unsafe { core::LLVMBuildExtractElement(self.into(), vec.into(), index.into(), NULL_NAME.as_ptr()).into() }
+    }
+    /// Build an instruction that yields the vector `vec` with the element at `index` replaced by `elem`.
+    pub fn build_insert_element(&self, vec: &Value, elem: &Value, index: &Value) -> &Value {
+        let vec_ty = get_vector_type(vec.get_type());
+        assert!(vec_ty.get_element() == elem.get_type(), "cannot insert {:?} into {:?}", elem.get_type(), vec_ty);
+        assert!(index.get_type().is_integer(), "expected integer index, got {:?}", index.get_type());
+        unsafe { core::LLVMBuildInsertElement(self.into(), vec.into(), elem.into(), index.into(), NULL_NAME.as_ptr()).into() }
+    }
+    /// Build an instruction that makes a vector from elements of `left` and `right`.
+    ///
+    /// The mask must be a constant vector of `i32`, where each element is the index of
+    /// the element to pick, counting through the elements of `left` then `right`.
+    pub fn build_shuffle_vector(&self, left: &Value, right: &Value, mask: &Value) -> &Value {
+        let ty = get_vector_type(left.get_type());
+        assert!(right.get_type() == &**ty, "cannot shuffle {:?} with {:?}", ty, right.get_type());
+        let mask_ty = get_vector_type(mask.get_type());
+        assert!(mask.is_constant() && mask_ty.get_element().get_int_width() == Some(32), "expected a constant vector of i32 as the mask, got {:?}", mask);
+        unsafe { core::LLVMBuildShuffleVector(self.into(), left.into(), right.into(), mask.into(), NULL_NAME.as_ptr()).into() }
+    }
     /// Build an instruction that computes the address of a subelement of an aggregate data structure.
     ///
     /// Basically type-safe pointer arithmetic.
     pub fn build_gep(&self, pointer: &Value, indices: &[&Value]) -> &Value {
         unsafe { core::LLVMBuildInBoundsGEP(self.into(), pointer.into(), indices.as_ptr() as *mut LLVMValueRef, indices.len() as c_uint, NULL_NAME.as_ptr()).into() }
     }
+    /// Build an instruction that computes the address of the field at `index` in the struct
+    /// pointed to by `pointer`.
+    pub fn build_struct_gep(&self, pointer: &Value, index: u32) -> &Value {
+        let ty = pointer.get_type();
+        let fields = match PointerType::from_super(ty).and_then(|ptr| StructType::from_super(ptr.get_element())) {
+            Some(struct_ty) => struct_ty.get_elements().len(),
+            None => panic!("expected pointer to struct, got {:?}", ty)
+        };
+        assert!((index as usize) < fields, "no such field {} on {:?}", index, ty);
+        unsafe { core::LLVMBuildStructGEP(self.into(), pointer.into(), index as c_uint, NULL_NAME.as_ptr()).into() }
+    }
     /// Build an instruction that runs whichever block matches the value, or `default` if none of them matched it.
-    pub fn build_switch(&self, value: &Value, default: &BasicBlock, cases: &[(&Value, &BasicBlock)]) -> &Value {
-        unsafe {
-            let switch = core::LLVMBuildSwitch(self.into(), value.into(), default.into(), cases.len() as c_uint);
-            for case in cases {
-                core::LLVMAddCase(switch, case.0.into(), case.1.into());
-            }
-            switch.into()
+    ///
+    /// More cases can be added to the switch afterwards with `Switch::add_case`.
+    pub fn build_switch(&self, value: &Value, default: &BasicBlock, cases: &[(&Value, &BasicBlock)]) -> &Switch {
+        let switch: &Switch = unsafe { core::LLVMBuildSwitch(self.into(), value.into(), default.into(), cases.len() as c_uint).into() };
+        for &(on_val, dest) in cases {
+            switch.add_case(on_val, dest);
         }
+        switch
     }
 
-    un_op!{build_load, LLVMBuildLoad}
     un_op!{build_neg, LLVMBuildNeg}
+    un_op!{build_nsw_neg, LLVMBuildNSWNeg}
+    un_op!{build_nuw_neg, LLVMBuildNUWNeg}
     un_op!{build_fneg, LLVMBuildFNeg}
     un_op!{build_not, LLVMBuildNot}
     bin_op!{build_add, LLVMBuildAdd, LLVMBuildFAdd}
     bin_op!{build_sub, LLVMBuildSub, LLVMBuildFSub}
     bin_op!{build_mul, LLVMBuildMul, LLVMBuildFMul}
     bin_op!{build_div, LLVMBuildSDiv, LLVMBuildFDiv}
+    bin_op!{int build_nsw_add, LLVMBuildNSWAdd}
+    bin_op!{int build_nuw_add, LLVMBuildNUWAdd}
+    bin_op!{int build_nsw_sub, LLVMBuildNSWSub}
+    bin_op!{int build_nuw_sub, LLVMBuildNUWSub}
+    bin_op!{int build_nsw_mul, LLVMBuildNSWMul}
+    bin_op!{int build_nuw_mul, LLVMBuildNUWMul}
+    bin_op!{build_sdiv, LLVMBuildSDiv}
     bin_op!{build_udiv, LLVMBuildUDiv}
     bin_op!{build_urem, LLVMBuildURem}
     bin_op!{build_srem, LLVMBuildSRem}
-    bin_op!{build_shl, LLVMBuildShl}
-    bin_op!{build_ashr, LLVMBuildAShr}
-    bin_op!{build_lshr, LLVMBuildLShr}
-    bin_op!{build_and, LLVMBuildAnd}
-    bin_op!{build_or, LLVMBuildOr}
-    bin_op!{build_xor, LLVMBuildXor}
+    bin_op!{float build_fadd, LLVMBuildFAdd}
+    bin_op!{float build_fsub, LLVMBuildFSub}
+    bin_op!{float build_fmul, LLVMBuildFMul}
+    bin_op!{float build_fdiv, LLVMBuildFDiv}
+    bin_op!{float build_frem, LLVMBuildFRem}
+    bin_op!{int build_shl, LLVMBuildShl}
+    bin_op!{int build_ashr, LLVMBuildAShr}
+    bin_op!{int build_lshr, LLVMBuildLShr}
+    bin_op!{int build_and, LLVMBuildAnd}
+    bin_op!{int build_or, LLVMBuildOr}
+    bin_op!{int build_xor, LLVMBuildXor}
     /// Build an instruction to compare the values `a` and `b` with the predicate / comparative operator `pred`.
     pub fn build_signed_cmp(&self, a: &Value, b: &Value, pred: Predicate) -> &Value {
         let (at, bt) = (a.get_type(), b.get_type());
@@ -262,6 +580,22 @@ impl Builder {
         }
     }
 
+    /// Build an instruction to compare the integers `a` and `b` with the predicate `pred`.
+    ///
+    /// The result of this is an `i1`.
+    pub fn build_icmp(&self, pred: IntPredicate, a: &Value, b: &Value) -> &Value {
+        let (at, bt) = (a.get_type(), b.get_type());
+        assert!((is_integer(at) || at.is_pointer()) && at == bt, "expected integer or pointer operands, got {:?} and {:?}", at, bt);
+        unsafe { core::LLVMBuildICmp(self.into(), pred.into(), a.into(), b.into(), NULL_NAME.as_ptr()) }.into()
+    }
+    /// Build an instruction to compare the floating-point numbers `a` and `b` with the predicate `pred`.
+    ///
+    /// The result of this is an `i1`.
+    pub fn build_fcmp(&self, pred: RealPredicate, a: &Value, b: &Value) -> &Value {
+        let (at, bt) = (a.get_type(), b.get_type());
+        assert!(is_floating_point(at) && at == bt, "expected floating-point operands, got {:?} and {:?}", at, bt);
+        unsafe { core::LLVMBuildFCmp(self.into(), pred.into(), a.into(), b.into(), NULL_NAME.as_ptr()) }.into()
+    }
     pub fn build_unsigned_cmp(&self, a: &Value, b: &Value, pred: Predicate) -> &Value {
         let (at, bt) = (a.get_type(), b.get_type());
         assert_eq!(at, bt);
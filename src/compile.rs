@@ -181,7 +181,7 @@ macro_rules! compile_func(
                 }.into()
             }
             fn get_type(context: &'a Context) -> &'a Type {
-                FunctionType::new(R::get_type(context), &[$($name::get_type(context)),*])
+                FunctionType::new(R::get_type(context), &[$($name::get_type(context)),*], false)
             }
         }
         impl<'a, R, $($name),*> Compile<'a> for extern fn($($name),*) -> R where R:Compile<'a>, $($name:Compile<'a>),* {
@@ -193,7 +193,7 @@ macro_rules! compile_func(
                 }.into()
             }
             fn get_type(context: &'a Context) -> &'a Type {
-                FunctionType::new(R::get_type(context), &[$($name::get_type(context)),*])
+                FunctionType::new(R::get_type(context), &[$($name::get_type(context)),*], false)
             }
         }
     )
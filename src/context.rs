@@ -1,6 +1,8 @@
+use libc::{c_char, c_uint};
 use ffi::prelude::LLVMContextRef;
 use ffi::{core, LLVMContext};
 use std::marker::PhantomData;
+use std::ops::Deref;
 use cbox::CBox;
 
 /// Contains all the LLVM entities - mainly modules.
@@ -8,6 +10,10 @@ use cbox::CBox;
 /// Every single entity attached to it has its lifetime to enforce the
 /// rule that things from different contexts cannot interact and to
 /// preserve pointer safety.
+///
+/// A context and everything in it can only be used by one thread at a time.
+/// Since the entities borrow the context, they can't be sent to another thread
+/// on their own; use `ThreadSafeContext` to move a whole context between threads.
 pub struct Context(PhantomData<[u8]>);
 native_ref!(&Context = LLVMContextRef);
 impl Context {
@@ -22,9 +28,33 @@ impl Context {
     pub fn new() -> CBox<Self> {
         CBox::new(unsafe { core::LLVMContextCreate() })
     }
+    /// Returns the identifier of the metadata kind with the name given, such as `dbg`,
+    /// or registers a new kind if there is none with this name.
+    pub fn get_metadata_kind_id(&self, name: &str) -> u32 {
+        unsafe { core::LLVMGetMDKindIDInContext(self.into(), name.as_ptr() as *const c_char, name.len() as c_uint) as u32 }
+    }
 }
 dispose!(Context, LLVMContext, core::LLVMContextDispose);
 
+/// An owned context that can be moved to another thread.
+///
+/// This is safe because it is the only owner of the context, so nothing else can
+/// use the context while it is being used by the thread it was moved to.
+pub struct ThreadSafeContext(CBox<Context>);
+unsafe impl Send for ThreadSafeContext {}
+impl ThreadSafeContext {
+    /// Create a new context that can be moved to another thread.
+    pub fn new() -> ThreadSafeContext {
+        ThreadSafeContext(Context::new())
+    }
+}
+impl Deref for ThreadSafeContext {
+    type Target = Context;
+    fn deref(&self) -> &Context {
+        &self.0
+    }
+}
+
 /// Implemented by everything that is owned by a context.
 pub trait GetContext {
     /// Returns a reference to the context that owns this value.
@@ -1,14 +1,14 @@
-use libc::{c_int, c_uint, c_ulonglong};
-use ffi::{core, target};
+use libc::{c_int, c_uint, c_ulonglong, c_void};
+use ffi::core;
 use ffi::execution_engine as engine;
 use ffi::execution_engine::*;
-use ffi::target_machine::LLVMCodeModel;
 use cbox::{CBox, CSemiBox, DisposeRef};
 use std::marker::PhantomData;
 use std::{mem, ptr};
 use compile::Compile;
 use context::{Context, GetContext};
 use module::Module;
+use target::initialize_native_target;
 use types::{StructType, Type};
 use util::{self, Sub};
 use value::{Function, Value};
@@ -35,6 +35,14 @@ pub trait ExecutionEngine<'a>:'a + Sized + DisposeRef where LLVMExecutionEngineR
             out.assume_init().into()
         }
     }
+    /// Map the global value given, which should be a declaration in one of this engine's
+    /// modules, to the address given.
+    ///
+    /// This lets compiled code call into functions or use globals from the host program.
+    /// This should be done before any code using the global is compiled.
+    fn add_global_mapping(&'a self, global: &'a Value, address: *const ()) {
+        unsafe { engine::LLVMAddGlobalMapping(self.into(), global.into(), address as *mut c_void) }
+    }
     /// Execute all of the static constructors for this program.
     fn run_static_constructors(&'a self) {
         unsafe { engine::LLVMRunStaticConstructors(self.into()) }
@@ -52,6 +60,18 @@ pub trait ExecutionEngine<'a>:'a + Sized + DisposeRef where LLVMExecutionEngineR
             mem::transmute(out)
         })
     }
+    /// Returns the address of the compiled function with the name given, or `None` if there
+    /// wasn't a function with that name.
+    ///
+    /// The address can be transmuted into a function pointer of the right signature.
+    fn get_function_address(&'a self, name: &str) -> Option<u64> {
+        util::with_cstr(name, |c_name| unsafe {
+            match engine::LLVMGetFunctionAddress(self.into(), c_name) {
+                0 => None,
+                address => Some(address as u64)
+            }
+        })
+    }
     /// Run `function` with the arguments given as ``GenericValue`s, then return the result as one.
     ///
     /// Note that if this engine is a `JitEngine`, it only supports a small fraction of combinations
@@ -59,9 +79,11 @@ pub trait ExecutionEngine<'a>:'a + Sized + DisposeRef where LLVMExecutionEngineR
     ///
     /// To convert the arguments to `GenericValue`s, you should use the `GenericValueCast::to_generic` method.
     /// To convert the return value from a `GenericValue`, you should use the `GenericValueCast::from_generic` method.
-    fn run_function(&'a self, function: &'a Function, args: &[&'a GenericValue]) -> &'a GenericValue {
+    ///
+    /// The result is owned by the caller, so it will be disposed when it is dropped.
+    fn run_function(&'a self, function: &'a Function, args: &[&'a GenericValue]) -> CSemiBox<'a, GenericValue> {
         let ptr = args.as_ptr() as *mut LLVMGenericValueRef;
-        unsafe { engine::LLVMRunFunction(self.into(), function.into(), args.len() as c_uint, ptr).into() }
+        unsafe { CSemiBox::new(engine::LLVMRunFunction(self.into(), function.into(), args.len() as c_uint, ptr)) }
     }
     /// Returns a pointer to the global value given.
     ///
@@ -137,20 +159,14 @@ impl<'a> ExecutionEngine<'a> for JitEngine {
             let mut ee = mem::MaybeUninit::uninit();
             let mut out = mem::zeroed();
             engine::LLVMLinkInMCJIT();
-            if target::LLVM_InitializeNativeTarget() == 1 {
-                return Err("failed to initialize native target".into())
-            }
-            if target::LLVM_InitializeNativeAsmPrinter() == 1 {
-                return Err("failed to initialize native asm printer".into())
-            }
-            let mut options = LLVMMCJITCompilerOptions {
-                OptLevel: options.opt_level as c_uint,
-                CodeModel: LLVMCodeModel::LLVMCodeModelDefault,
-                NoFramePointerElim: 0,
-                EnableFastISel: 1,
-                MCJMM: ptr::null_mut()
-            };
+            initialize_native_target()?;
+            let opt_level = options.opt_level;
             let size = mem::size_of::<LLVMMCJITCompilerOptions>();
+            let mut options: LLVMMCJITCompilerOptions = mem::zeroed();
+            // let LLVM fill in its defaults before anything is changed
+            engine::LLVMInitializeMCJITCompilerOptions(&mut options, size);
+            options.OptLevel = opt_level as c_uint;
+            options.EnableFastISel = 1;
             let result = engine::LLVMCreateMCJITCompilerForModule(ee.as_mut_ptr(), (&*module).into(), &mut options, size, &mut out);
             if result == 0 {
                 Ok(ee.assume_init().into())
@@ -184,6 +200,26 @@ impl<'a> ExecutionEngine<'a> for Interpreter {
 pub struct GenericValue(PhantomData<[u8]>);
 native_ref!{&GenericValue = LLVMGenericValueRef}
 dispose!{GenericValue, LLVMOpaqueGenericValue, LLVMDisposeGenericValue}
+impl GenericValue {
+    /// Create a `GenericValue` holding the integer `value` of the integer type given.
+    ///
+    /// If `signed` is true, the value is sign extended to fit the type.
+    pub fn from_int<'a>(ty: &'a Type, value: u64, signed: bool) -> CSemiBox<'a, GenericValue> {
+        unsafe { CSemiBox::new(engine::LLVMCreateGenericValueOfInt(ty.into(), value as c_ulonglong, signed as c_int)) }
+    }
+    /// Create a `GenericValue` holding the floating-point number `value` of the floating-point type given.
+    pub fn from_float<'a>(ty: &'a Type, value: f64) -> CSemiBox<'a, GenericValue> {
+        unsafe { CSemiBox::new(engine::LLVMCreateGenericValueOfFloat(ty.into(), value)) }
+    }
+    /// Returns the integer this holds, sign extending it if `signed` is true.
+    pub fn to_int(&self, signed: bool) -> u64 {
+        unsafe { engine::LLVMGenericValueToInt(self.into(), signed as c_int) as u64 }
+    }
+    /// Returns the floating-point number of the type given that this holds.
+    pub fn to_float(&self, ty: &Type) -> f64 {
+        unsafe { engine::LLVMGenericValueToFloat(ty.into(), self.into()) }
+    }
+}
 
 /// A value that can be cast into a `GenericValue` and that a `GenericValue` can be cast into.
 ///
@@ -19,6 +19,7 @@ mod context;
 mod engine;
 mod module;
 mod object;
+mod pass;
 mod target;
 pub mod types;
 pub mod value;
@@ -26,13 +27,15 @@ mod util;
 
 pub use cbox::{CBox, CSemiBox};
 pub use builder::Builder;
-pub use block::BasicBlock;
+pub use block::{BasicBlock, Instructions};
+pub use buffer::MemoryBuffer;
 pub use compile::Compile;
-pub use context::{Context, GetContext};
+pub use context::{Context, GetContext, ThreadSafeContext};
 pub use engine::{JitEngine, JitOptions, Interpreter, ExecutionEngine, GenericValue, GenericValueCast};
-pub use module::{AddressSpace, Module, Functions};
+pub use module::{AddressSpace, Module, Functions, ThreadSafeModule};
 pub use object::{ObjectFile, Symbol, Symbols};
-pub use target::{TargetData, Target, get_default_target_triple};
+pub use pass::{PassManager, PassManagerBuilder};
+pub use target::{CodeGenOptLevel, CodeModel, FileType, RelocMode, TargetData, Target, TargetMachine, get_default_target_triple, initialize_all_targets, initialize_native_target};
 pub use types::*;
-pub use value::{Alias, Arg, Attribute, Value, Function, GlobalValue, GlobalVariable, Linkage, Predicate};
+pub use value::{Alias, Arg, AtomicOrdering, AtomicRMWBinOp, Attribute, AttributeIndex, Call, CallConv, EnumAttribute, Value, Function, GlobalValue, GlobalVariable, IntPredicate, LandingPad, Linkage, Opcode, Params, PhiNode, Predicate, RealPredicate, StringAttribute, Switch, Use, Uses, ValueKind, Visibility};
 pub use util::Sub;
@@ -158,10 +158,12 @@ macro_rules! to_str(
     ($ty:ty, $func:ident) => (
         impl fmt::Debug for $ty {
             fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-                fmt.write_str(unsafe {
+                unsafe {
                     let c_str = core::$func(self.into());
-                    util::to_str(c_str)
-                })
+                    let result = fmt.write_str(util::to_str(c_str));
+                    core::LLVMDisposeMessage(c_str);
+                    result
+                }
             }
         }
     );
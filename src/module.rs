@@ -1,12 +1,10 @@
-use libc::{c_char, c_uint};
+use libc::c_char;
 use ffi::prelude::{LLVMValueRef, LLVMModuleRef};
 use ffi::analysis::LLVMVerifierFailureAction;
-use ffi::{analysis, core, linker, LLVMModule};
-use ffi::transforms::pass_manager_builder as builder;
+use ffi::{analysis, core, ir_reader, linker, LLVMModule};
 use ffi::bit_writer as writer;
 use ffi::bit_reader as reader;
 use cbox::{CBox, CSemiBox};
-use std::ffi::CString;
 use std::iter::{Iterator, IntoIterator};
 use std::io::{Error, ErrorKind};
 use std::io::Result as IoResult;
@@ -15,10 +13,11 @@ use std::marker::PhantomData;
 use std::path::Path;
 use std::process::Command;
 use buffer::MemoryBuffer;
-use context::{Context, GetContext};
+use pass::{PassManager, PassManagerBuilder};
+use context::{Context, GetContext, ThreadSafeContext};
 use value::{Alias, Function, GlobalValue, GlobalVariable, Value};
-use types::Type;
-use util;
+use types::{ArrayType, PointerType, StructType, Type, TypeKind, VectorType};
+use util::{self, Sub, NULL_NAME};
 
 /// Represents a single compilation unit of code.
 ///
@@ -39,8 +38,15 @@ impl Module {
     /// println!("{:?}", module)
     /// ```
     pub fn new<'a>(name: &str, context: &'a Context) -> CSemiBox<'a, Module> {
-        let c_name = CString::new(name).unwrap();
-        unsafe { CSemiBox::new(core::LLVMModuleCreateWithNameInContext(c_name.as_ptr(), context.into())) }
+        Module::try_new(name, context).unwrap_or_else(|err| panic!("{}", &*err))
+    }
+    /// Create a new module in the context given with the name given, or return an error
+    /// if the name contains a nul byte.
+    ///
+    /// This is the non-panicking version of `Module::new`.
+    pub fn try_new<'a>(name: &str, context: &'a Context) -> Result<CSemiBox<'a, Module>, CBox<str>> {
+        let c_name = util::to_cstring(name)?;
+        Ok(unsafe { CSemiBox::new(core::LLVMModuleCreateWithNameInContext(c_name.as_ptr(), context.into())) })
     }
     /// Dump the module to stderr (for debugging purposes)
     pub fn dump(&self) {
@@ -66,8 +72,12 @@ impl Module {
         global.set_initializer(val);
         global
     }
-    /// Add a global to the module with the given type and name.
+    /// Add an alias with the name given to the global value `val`, which must be in this module.
+    ///
+    /// The alias has the same type as `val` and refers to the same address.
     pub fn add_global_alias<'a>(&'a self, name: &str, val: &'a GlobalValue) -> &'a Alias {
+        let (parent, module): (LLVMModuleRef, LLVMModuleRef) = (unsafe { core::LLVMGetGlobalParent(val.into()) }, self.into());
+        assert!(parent == module, "cannot alias {:?}, which is in another module", val.get_name());
         util::with_cstr(name, |ptr| unsafe {
             core::LLVMAddAlias(self.into(), val.get_type().into(), val.into(), ptr).into()
         })
@@ -80,7 +90,11 @@ impl Module {
         })
     }
     /// Parse this bitcode file into a module, or return an error string.
-    pub fn parse_bitcode<'a>(context: &'a Context, path: &str) -> Result<CSemiBox<'a, Module>, CBox<str>> {
+    pub fn parse_bitcode<'a, P>(context: &'a Context, path: P) -> Result<CSemiBox<'a, Module>, CBox<str>> where P: AsRef<Path> {
+        let path = match path.as_ref().to_str() {
+            Some(path) => path,
+            None => return Err("path is not valid unicode".into())
+        };
         unsafe {
             let mut out = mem::MaybeUninit::uninit();
             let mut err = mem::MaybeUninit::uninit();
@@ -92,24 +106,113 @@ impl Module {
             }
         }
     }
+    /// Parse the textual LLVM IR given into a module, or return an error string.
+    pub fn parse_ir<'a>(context: &'a Context, text: &str) -> Result<CSemiBox<'a, Module>, CBox<str>> {
+        unsafe {
+            let mut out = mem::MaybeUninit::uninit();
+            let mut err = mem::MaybeUninit::uninit();
+            let ptr = text.as_ptr() as *const c_char;
+            let buf = core::LLVMCreateMemoryBufferWithMemoryRangeCopy(ptr, text.len(), NULL_NAME.as_ptr());
+            // the buffer is owned and disposed by LLVM from here on
+            if ir_reader::LLVMParseIRInContext(context.into(), buf, out.as_mut_ptr(), err.as_mut_ptr()) == 1 {
+                Err(CBox::new(err.assume_init()))
+            } else {
+                Ok(CSemiBox::new(out.assume_init()))
+            }
+        }
+    }
     /// Write this module's bitcode to the path given.
-    pub fn write_bitcode(&self, path: &str) -> IoResult<()> {
-        util::with_cstr(path, |cpath| unsafe {
+    pub fn write_bitcode<P>(&self, path: P) -> IoResult<()> where P: AsRef<Path> {
+        let path = path.as_ref();
+        let path_str = match path.to_str() {
+            Some(path) => path,
+            None => return Err(Error::new(ErrorKind::InvalidInput, "path is not valid unicode"))
+        };
+        util::with_cstr(path_str, |cpath| unsafe {
             if writer::LLVMWriteBitcodeToFile(self.into(), cpath) != 0 {
-                Err(Error::new(ErrorKind::Other, &format!("could not write to {}", path) as &str))
+                Err(Error::new(ErrorKind::Other, &format!("could not write to {}", path.display()) as &str))
             } else {
                 Ok(())
             }
         })
     }
+    /// Returns the textual IR of this module.
+    pub fn to_string(&self) -> String {
+        unsafe {
+            let c_str = core::LLVMPrintModuleToString(self.into());
+            let text = util::to_str(c_str).to_owned();
+            core::LLVMDisposeMessage(c_str);
+            text
+        }
+    }
+    /// Write the textual IR of this module to the path given, or return an error string.
+    pub fn print_to_file<P>(&self, path: P) -> Result<(), CBox<str>> where P: AsRef<Path> {
+        let path = match path.as_ref().to_str() {
+            Some(path) => path,
+            None => return Err("path is not valid unicode".into())
+        };
+        util::with_cstr(path, |cpath| unsafe {
+            let mut error = mem::MaybeUninit::uninit();
+            if core::LLVMPrintModuleToFile(self.into(), cpath, error.as_mut_ptr()) == 1 {
+                Err(CBox::new(error.assume_init()))
+            } else {
+                Ok(())
+            }
+        })
+    }
+    /// Iterate through the functions in this module, including declarations.
+    pub fn functions(&self) -> Functions {
+        self.into_iter()
+    }
     /// Add a function to the module with the name given.
+    ///
+    /// The signature `sig` should be a function type, such as one made by `FunctionType::new`.
     pub fn add_function<'a>(&'a self, name: &str, sig: &'a Type) -> &'a mut Function {
-        let c_name = CString::new(name).unwrap();
-        unsafe { core::LLVMAddFunction(self.into(), c_name.as_ptr(), sig.into()) }.into()
+        self.try_add_function(name, sig).unwrap_or_else(|err| panic!("{}", &*err))
+    }
+    /// Add a function to the module with the name given, or return an error if the name
+    /// contains a nul byte.
+    ///
+    /// This is the non-panicking version of `add_function`.
+    pub fn try_add_function<'a>(&'a self, name: &str, sig: &'a Type) -> Result<&'a mut Function, CBox<str>> {
+        let c_name = util::to_cstring(name)?;
+        Ok(unsafe { core::LLVMAddFunction(self.into(), c_name.as_ptr(), sig.into()) }.into())
+    }
+    /// Returns the declaration of the intrinsic with the name given, such as `llvm.sqrt`,
+    /// declaring it with the signature `sig` if it hasn't been declared yet.
+    ///
+    /// Overloaded intrinsics have the types in `param_types` mangled into their name, so
+    /// `llvm.sqrt` with `double` is declared as `llvm.sqrt.f64`. The C API this binds has no
+    /// `LLVMGetIntrinsicDeclaration` to look the signature up, so it must be given.
+    ///
+    /// This returns `None` if LLVM doesn't recognise the mangled name as an intrinsic, and
+    /// panics if it has already been declared with a different signature.
+    pub fn get_intrinsic_declaration<'a>(&'a self, name: &str, param_types: &[&Type], sig: &'a Type) -> Option<&'a Function> {
+        let mut mangled = name.to_string();
+        for ty in param_types {
+            mangled.push('.');
+            mangled.push_str(&mangle_type(ty));
+        }
+        let intrinsic: &Function = match self.get_function(&mangled) {
+            Some(existing) => {
+                let found: &Type = existing.get_signature();
+                assert!(found == sig, "expected {} to have type {:?}, got {:?}", mangled, sig, found);
+                existing
+            },
+            None => {
+                let declared = self.add_function(&mangled, sig);
+                if declared.get_intrinsic_id().is_none() {
+                    unsafe { declared.delete() };
+                    return None
+                }
+                &*declared
+            }
+        };
+        intrinsic.get_intrinsic_id().map(|_| intrinsic)
     }
     /// Returns the function with the name given, or `None` if no function with that name exists.
     pub fn get_function<'a>(&'a self, name: &str) -> Option<&'a Function> {
-        let c_name = CString::new(name).unwrap();
+        let c_name = util::to_cstring(name).ok()?;
         unsafe {
             let ty = core::LLVMGetNamedFunction(self.into(), c_name.as_ptr());
             util::ptr_to_null(ty)
@@ -117,13 +220,47 @@ impl Module {
     }
     /// Returns the type with the name given, or `None`` if no type with that name exists.
     pub fn get_type<'a>(&'a self, name: &str) -> Option<&'a Type> {
-        let c_name = CString::new(name).unwrap();
+        let c_name = util::to_cstring(name).ok()?;
         unsafe {
             let ty = core::LLVMGetTypeByName(self.into(), c_name.as_ptr());
             util::ptr_to_null(ty)
         }
     }
-    /// Clone this module.
+    /// Add the metadata node given to the end of the named metadata list with the name given,
+    /// such as `llvm.module.flags`, creating the list if it doesn't exist yet.
+    pub fn add_named_metadata_operand(&self, name: &str, node: &Value) {
+        self.try_add_named_metadata_operand(name, node).unwrap_or_else(|err| panic!("{}", &*err))
+    }
+    /// Add the metadata node given to the named metadata list with the name given, or
+    /// return an error if the name contains a nul byte.
+    ///
+    /// This is the non-panicking version of `add_named_metadata_operand`.
+    pub fn try_add_named_metadata_operand(&self, name: &str, node: &Value) -> Result<(), CBox<str>> {
+        let c_name = util::to_cstring(name)?;
+        unsafe { core::LLVMAddNamedMetadataOperand(self.into(), c_name.as_ptr(), node.into()) }
+        Ok(())
+    }
+    /// Returns the metadata nodes in the named metadata list with the name given, which
+    /// will be empty if there is no list with that name.
+    pub fn get_named_metadata_operands<'a>(&'a self, name: &str) -> Vec<&'a Value> {
+        self.try_get_named_metadata_operands(name).unwrap_or_else(|err| panic!("{}", &*err))
+    }
+    /// Returns the metadata nodes in the named metadata list with the name given, or an
+    /// error if the name contains a nul byte.
+    ///
+    /// This is the non-panicking version of `get_named_metadata_operands`.
+    pub fn try_get_named_metadata_operands<'a>(&'a self, name: &str) -> Result<Vec<&'a Value>, CBox<str>> {
+        let c_name = util::to_cstring(name)?;
+        unsafe {
+            let count = core::LLVMGetNamedMetadataNumOperands(self.into(), c_name.as_ptr()) as usize;
+            let mut nodes: Vec<LLVMValueRef> = Vec::with_capacity(count);
+            core::LLVMGetNamedMetadataOperands(self.into(), c_name.as_ptr(), nodes.as_mut_ptr());
+            nodes.set_len(count);
+            Ok(nodes.into_iter().map(|node| node.into()).collect())
+        }
+    }
+    /// Make an independent copy of this module in the same context, including its
+    /// functions, globals and metadata.
     pub fn clone<'a>(&'a self) -> CSemiBox<'a, Module> {
         CSemiBox::new(unsafe { core::LLVMCloneModule(self.into()) })
     }
@@ -132,18 +269,15 @@ impl Module {
     ///
     /// This runs passes depending on the levels given.
     pub fn optimize(&self, opt_level: usize, size_level: usize) {
-        unsafe {
-            let builder = builder::LLVMPassManagerBuilderCreate();
-            builder::LLVMPassManagerBuilderSetOptLevel(builder, opt_level as c_uint);
-            builder::LLVMPassManagerBuilderSetSizeLevel(builder, size_level as c_uint);
-            let pass_manager = core::LLVMCreatePassManager();
-            builder::LLVMPassManagerBuilderPopulateModulePassManager(builder, pass_manager);
-            builder::LLVMPassManagerBuilderDispose(builder);
-            core::LLVMRunPassManager(pass_manager, self.into());
-        }
+        let builder = PassManagerBuilder::new();
+        builder.set_opt_level(opt_level as u32);
+        builder.set_size_level(size_level as u32);
+        let passes = PassManager::new();
+        builder.populate_module_pass_manager(&passes);
+        passes.run(self);
     }
 
-    /// Returns the target data of this module represented as a string
+    /// Returns the target triple of this module, which will be empty if it has not been set.
     pub fn get_target(&self) -> &str {
         unsafe {
             let target = core::LLVMGetTarget(self.into());
@@ -151,10 +285,34 @@ impl Module {
         }
     }
 
-    /// Set the target data of this module to the target data string given.
+    /// Set the target triple of this module to the triple given.
     pub fn set_target(&self, target: &str) {
-        let c_target = CString::new(target).unwrap();
+        self.try_set_target(target).unwrap_or_else(|err| panic!("{}", &*err))
+    }
+
+    /// Set the target triple of this module to the triple given, or return an error if it
+    /// contains a nul byte.
+    ///
+    /// This is the non-panicking version of `set_target`.
+    pub fn try_set_target(&self, target: &str) -> Result<(), CBox<str>> {
+        let c_target = util::to_cstring(target)?;
         unsafe { core::LLVMSetTarget(self.into(), c_target.as_ptr()) }
+        Ok(())
+    }
+
+    /// Returns the data layout string of this module, which will be empty if it has not been set.
+    pub fn get_data_layout(&self) -> &str {
+        unsafe {
+            let layout = core::LLVMGetDataLayout(self.into());
+            util::to_str(layout as *mut c_char)
+        }
+    }
+
+    /// Set the data layout of this module to the data layout string given.
+    pub fn set_data_layout(&self, layout: &str) {
+        util::with_cstr(layout, |ptr| unsafe {
+            core::LLVMSetDataLayout(self.into(), ptr)
+        })
     }
 
     /// Verify that the module is safe to run, returning a string detailing the error
@@ -166,6 +324,10 @@ impl Module {
             if analysis::LLVMVerifyModule(self.into(), action, error.as_mut_ptr()) == 1 {
                 Err(CBox::new(error.assume_init()))
             } else {
+                let error = error.assume_init();
+                if !error.is_null() {
+                    core::LLVMDisposeMessage(error);
+                }
                 Ok(())
             }
         }
@@ -206,9 +368,10 @@ impl Module {
         }
     }
 
-    /// Link a module into this module, returning an error string if an error occurs.
+    /// Link a module into this module, returning an error string if an error occurs,
+    /// such as when both modules define the same symbol.
     ///
-    /// This *does* destroy the source module.
+    /// This *does* destroy the source module, so it is taken by value.
     pub fn link_destroy(&self, src: CSemiBox<Module>) -> Result<(), CBox<str>> {
         unsafe {
             let dest = self.into();
@@ -233,9 +396,79 @@ impl<'a> IntoIterator for &'a Module {
         }
     }
 }
+/// Returns the suffix LLVM uses for the type given in the names of overloaded intrinsics.
+fn mangle_type(ty: &Type) -> String {
+    if let Some(ptr) = PointerType::from_super(ty) {
+        format!("p{}{}", ptr.get_address_space(), mangle_type(ptr.get_element()))
+    } else if let Some(array) = ArrayType::from_super(ty) {
+        format!("a{}{}", array.get_length(), mangle_type(array.get_element()))
+    } else if let Some(vec) = VectorType::from_super(ty) {
+        format!("v{}{}", vec.get_size(), mangle_type(vec.get_element()))
+    } else if let Some(struct_ty) = StructType::from_super(ty) {
+        match struct_ty.get_name() {
+            Some(name) => format!("s_{}", name),
+            None => format!("sl_{}s", struct_ty.get_elements().into_iter().map(mangle_type).collect::<String>())
+        }
+    } else if let Some(width) = ty.get_int_width() {
+        format!("i{}", width)
+    } else {
+        match ty.get_kind() {
+            TypeKind::Half => "f16",
+            TypeKind::Float => "f32",
+            TypeKind::Double => "f64",
+            TypeKind::X86FP80 => "f80",
+            TypeKind::FP128 => "f128",
+            TypeKind::PPCFP128 => "ppcf128",
+            TypeKind::Void => "isVoid",
+            TypeKind::Metadata => "Metadata",
+            _ => panic!("can't mangle {:?} into an intrinsic name", ty)
+        }.to_string()
+    }
+}
 get_context!(Module, LLVMGetModuleContext);
 to_str!(Module, LLVMPrintModuleToString);
 dispose!(Module, LLVMModule, core::LLVMDisposeModule);
+/// A module bundled with the context that owns it, which can be moved to another thread.
+///
+/// This lets a module be built on a worker thread then handed back, since the
+/// module and its context are always moved together.
+pub struct ThreadSafeModule {
+    module: LLVMModuleRef,
+    context: ThreadSafeContext
+}
+unsafe impl Send for ThreadSafeModule {}
+impl ThreadSafeModule {
+    /// Create a new empty module with the name given in the context given.
+    pub fn new(context: ThreadSafeContext, name: &str) -> ThreadSafeModule {
+        ThreadSafeModule::try_new(context, name).unwrap_or_else(|err| panic!("{}", &*err))
+    }
+    /// Create a new empty module with the name given in the context given, or return an
+    /// error if the name contains a nul byte.
+    ///
+    /// This is the non-panicking version of `ThreadSafeModule::new`.
+    pub fn try_new(context: ThreadSafeContext, name: &str) -> Result<ThreadSafeModule, CBox<str>> {
+        let c_name = util::to_cstring(name)?;
+        Ok(ThreadSafeModule {
+            module: unsafe { core::LLVMModuleCreateWithNameInContext(c_name.as_ptr(), (&*context).into()) },
+            context: context
+        })
+    }
+    /// Returns the module.
+    pub fn get_module(&self) -> &Module {
+        self.module.into()
+    }
+    /// Returns the context that owns the module.
+    pub fn get_context(&self) -> &Context {
+        &self.context
+    }
+}
+impl Drop for ThreadSafeModule {
+    fn drop(&mut self) {
+        // the module must be disposed before its context is
+        unsafe { core::LLVMDisposeModule(self.module) }
+    }
+}
+
 #[derive(Copy, Clone)]
 /// An iterator through the functions contained in a module.
 pub struct Functions<'a> {
@@ -248,9 +481,9 @@ impl<'a> Iterator for Functions<'a> {
         if self.value.is_null() {
             None
         } else {
-            let c_next = unsafe { core::LLVMGetNextFunction(self.value) };
-            self.value = c_next;
-            Some(self.value.into())
+            let function = self.value;
+            self.value = unsafe { core::LLVMGetNextFunction(function) };
+            Some(function.into())
         }
     }
 }
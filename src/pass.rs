@@ -0,0 +1,66 @@
+use ffi::prelude::LLVMPassManagerRef;
+use ffi::{core, LLVMPassManager};
+use ffi::transforms::scalar;
+use ffi::transforms::pass_manager_builder::{self as builder, LLVMOpaquePassManagerBuilder, LLVMPassManagerBuilderRef};
+use libc::c_uint;
+use cbox::CBox;
+use std::marker::PhantomData;
+use module::Module;
+
+/// Runs a sequence of optimization passes over modules.
+pub struct PassManager(PhantomData<[u8]>);
+native_ref!(&PassManager = LLVMPassManagerRef);
+dispose!{PassManager, LLVMPassManager, core::LLVMDisposePassManager}
+impl PassManager {
+    /// Create a new pass manager with no passes.
+    pub fn new() -> CBox<PassManager> {
+        CBox::new(unsafe { core::LLVMCreatePassManager() })
+    }
+    /// Add a pass that combines redundant instructions.
+    pub fn add_instruction_combining(&self) {
+        unsafe { scalar::LLVMAddInstructionCombiningPass(self.into()) }
+    }
+    /// Add a pass that eliminates redundant values and loads using global value numbering.
+    pub fn add_gvn(&self) {
+        unsafe { scalar::LLVMAddGVNPass(self.into()) }
+    }
+    /// Add a pass that removes dead blocks and merges redundant ones.
+    pub fn add_cfg_simplification(&self) {
+        unsafe { scalar::LLVMAddCFGSimplificationPass(self.into()) }
+    }
+    /// Add a pass that promotes stack allocations to registers.
+    pub fn add_promote_memory_to_register(&self) {
+        unsafe { scalar::LLVMAddPromoteMemoryToRegisterPass(self.into()) }
+    }
+    /// Run the passes on the module given, returning true if it was modified.
+    pub fn run(&self, module: &Module) -> bool {
+        unsafe { core::LLVMRunPassManager(self.into(), module.into()) != 0 }
+    }
+}
+
+/// Populates pass managers with the standard optimization pipelines, like `-O2` does.
+pub struct PassManagerBuilder(PhantomData<[u8]>);
+native_ref!(&PassManagerBuilder = LLVMPassManagerBuilderRef);
+dispose!{PassManagerBuilder, LLVMOpaquePassManagerBuilder, builder::LLVMPassManagerBuilderDispose}
+impl PassManagerBuilder {
+    /// Create a new pass manager builder.
+    pub fn new() -> CBox<PassManagerBuilder> {
+        CBox::new(unsafe { builder::LLVMPassManagerBuilderCreate() })
+    }
+    /// Set the optimization level, between 0 and 3.
+    pub fn set_opt_level(&self, opt_level: u32) {
+        unsafe { builder::LLVMPassManagerBuilderSetOptLevel(self.into(), opt_level as c_uint) }
+    }
+    /// Set the size optimization level, between 0 and 2.
+    pub fn set_size_level(&self, size_level: u32) {
+        unsafe { builder::LLVMPassManagerBuilderSetSizeLevel(self.into(), size_level as c_uint) }
+    }
+    /// Make the pipeline inline functions whose cost is below `threshold`.
+    pub fn use_inliner_with_threshold(&self, threshold: u32) {
+        unsafe { builder::LLVMPassManagerBuilderUseInlinerWithThreshold(self.into(), threshold as c_uint) }
+    }
+    /// Add the passes for the configured pipeline to the pass manager given.
+    pub fn populate_module_pass_manager(&self, passes: &PassManager) {
+        unsafe { builder::LLVMPassManagerBuilderPopulateModulePassManager(self.into(), passes.into()) }
+    }
+}
@@ -1,10 +1,12 @@
 use libc::{c_char,  c_uint};
-use ffi::target_machine::{self, LLVMTargetRef};
+use ffi::target_machine::{self, LLVMCodeGenFileType, LLVMCodeGenOptLevel, LLVMCodeModel, LLVMOpaqueTargetMachine, LLVMRelocMode, LLVMTargetMachineRef, LLVMTargetRef};
 use ffi::target::{self, LLVMTargetDataRef, LLVMOpaqueTargetData};
 use cbox::{CBox, DisposeRef};
-use std::ffi::CString;
 use std::fmt;
 use std::marker::PhantomData;
+use std::mem;
+use std::path::Path;
+use module::Module;
 use types::Type;
 use util;
 
@@ -15,10 +17,17 @@ native_ref!(&TargetData = LLVMTargetDataRef);
 impl TargetData {
     /// Create a target data from a target layout string.
     pub fn new(rep: &str) -> CBox<TargetData> {
-        let c_rep = CString::new(rep).unwrap();
-        CBox::new(unsafe {
+        TargetData::try_new(rep).unwrap_or_else(|err| panic!("{}", &*err))
+    }
+    /// Create a target data from a target layout string, or return an error if it
+    /// contains a nul byte.
+    ///
+    /// This is the non-panicking version of `TargetData::new`.
+    pub fn try_new(rep: &str) -> Result<CBox<TargetData>, CBox<str>> {
+        let c_rep = util::to_cstring(rep)?;
+        Ok(CBox::new(unsafe {
             target::LLVMCreateTargetData(c_rep.as_ptr())
-        })
+        }))
     }
     /// Returns true if the target is big endian.
     pub fn is_big_endian(&self) -> bool {
@@ -69,9 +78,24 @@ impl DisposeRef for TargetData {
     }
 }
 
+/// A machine architecture that LLVM can generate code for.
 pub struct Target(PhantomData<[u8]>);
 native_ref!(&Target = LLVMTargetRef);
 impl Target {
+    /// Returns the target for the target triple given, or an error string if there isn't one.
+    ///
+    /// The target must have been initialized first for it to be found.
+    pub fn from_triple(triple: &str) -> Result<&'static Target, CBox<str>> {
+        util::with_cstr(triple, |c_triple| unsafe {
+            let mut target = mem::MaybeUninit::uninit();
+            let mut error = mem::MaybeUninit::uninit();
+            if target_machine::LLVMGetTargetFromTriple(c_triple, target.as_mut_ptr(), error.as_mut_ptr()) == 1 {
+                Err(CBox::new(error.assume_init()))
+            } else {
+                Ok(target.assume_init().into())
+            }
+        })
+    }
     /// Returns the name of this target.
     pub fn get_name(&self) -> &str {
         unsafe { util::to_str(target_machine::LLVMGetTargetName(self.into()) as *mut c_char) }
@@ -94,9 +118,152 @@ impl Target {
     }
 }
 
+/// Initialize the target for the machine this is running on, so that it can be
+/// looked up, compiled to and JIT compiled to.
+///
+/// This can safely be called multiple times.
+pub fn initialize_native_target() -> Result<(), CBox<str>> {
+    unsafe {
+        if target::LLVM_InitializeNativeTarget() == 1 {
+            return Err("failed to initialize native target".into())
+        }
+        if target::LLVM_InitializeNativeAsmPrinter() == 1 {
+            return Err("failed to initialize native asm printer".into())
+        }
+        if target::LLVM_InitializeNativeAsmParser() == 1 {
+            return Err("failed to initialize native asm parser".into())
+        }
+    }
+    Ok(())
+}
+
+/// Initialize every target LLVM was built with, for cross-compilation.
+///
+/// This can safely be called multiple times.
+pub fn initialize_all_targets() {
+    unsafe {
+        target::LLVM_InitializeAllTargetInfos();
+        target::LLVM_InitializeAllTargets();
+        target::LLVM_InitializeAllTargetMCs();
+        target::LLVM_InitializeAllAsmPrinters();
+        target::LLVM_InitializeAllAsmParsers();
+    }
+}
+
+/// Returns the target triple of the machine this is running on.
 pub fn get_default_target_triple() -> &'static str{
     unsafe {
         let s = target_machine::LLVMGetDefaultTargetTriple();
         util::to_str(s)
     }
 }
+
+/// How much optimization should be done when generating code.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum CodeGenOptLevel {
+    None = 0,
+    Less = 1,
+    Default = 2,
+    Aggressive = 3
+}
+impl From<CodeGenOptLevel> for LLVMCodeGenOptLevel {
+    fn from(level: CodeGenOptLevel) -> LLVMCodeGenOptLevel {
+        unsafe { mem::transmute(level) }
+    }
+}
+
+/// How the generated code should be relocated.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum RelocMode {
+    /// Use the default for the target.
+    Default = 0,
+    /// Non-relocatable code.
+    Static = 1,
+    /// Position independent code, for shared libraries.
+    PIC = 2,
+    /// Relocatable external references with non-relocatable code.
+    DynamicNoPic = 3
+}
+impl From<RelocMode> for LLVMRelocMode {
+    fn from(mode: RelocMode) -> LLVMRelocMode {
+        unsafe { mem::transmute(mode) }
+    }
+}
+
+/// The range of addresses the generated code and data can use.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum CodeModel {
+    /// Use the default for the target.
+    Default = 0,
+    /// Use the default for JIT compilation on the target.
+    JITDefault = 1,
+    Small = 2,
+    Kernel = 3,
+    Medium = 4,
+    Large = 5
+}
+impl From<CodeModel> for LLVMCodeModel {
+    fn from(model: CodeModel) -> LLVMCodeModel {
+        unsafe { mem::transmute(model) }
+    }
+}
+
+/// The kinds of file a target machine can emit.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum FileType {
+    /// A textual assembly file.
+    Assembly = 0,
+    /// A native object file.
+    Object = 1
+}
+impl From<FileType> for LLVMCodeGenFileType {
+    fn from(ty: FileType) -> LLVMCodeGenFileType {
+        unsafe { mem::transmute(ty) }
+    }
+}
+
+/// Generates machine code for a specific target, CPU and set of features.
+pub struct TargetMachine(PhantomData<[u8]>);
+native_ref!(&TargetMachine = LLVMTargetMachineRef);
+dispose!{TargetMachine, LLVMOpaqueTargetMachine, target_machine::LLVMDisposeTargetMachine}
+impl TargetMachine {
+    /// Create a new target machine for the target and triple given.
+    ///
+    /// The `cpu` and `features` can be left empty to use the defaults for the target.
+    pub fn new(target: &Target, triple: &str, cpu: &str, features: &str, opt_level: CodeGenOptLevel, reloc: RelocMode, code_model: CodeModel) -> CBox<TargetMachine> {
+        TargetMachine::try_new(target, triple, cpu, features, opt_level, reloc, code_model).unwrap_or_else(|err| panic!("{}", &*err))
+    }
+    /// Create a new target machine for the target and triple given, or return an error if
+    /// the triple, CPU or features contain a nul byte.
+    ///
+    /// This is the non-panicking version of `TargetMachine::new`.
+    pub fn try_new(target: &Target, triple: &str, cpu: &str, features: &str, opt_level: CodeGenOptLevel, reloc: RelocMode, code_model: CodeModel) -> Result<CBox<TargetMachine>, CBox<str>> {
+        let (c_triple, c_cpu, c_features) = (util::to_cstring(triple)?, util::to_cstring(cpu)?, util::to_cstring(features)?);
+        Ok(CBox::new(unsafe {
+            target_machine::LLVMCreateTargetMachine(target.into(), c_triple.as_ptr(), c_cpu.as_ptr(), c_features.as_ptr(), opt_level.into(), reloc.into(), code_model.into())
+        }))
+    }
+    /// Returns the target this machine generates code for.
+    pub fn get_target(&self) -> &Target {
+        unsafe { target_machine::LLVMGetTargetMachineTarget(self.into()).into() }
+    }
+    /// Generate code for the module given and write it to the path given, or return an error string.
+    pub fn emit_to_file<P>(&self, module: &Module, path: P, file_type: FileType) -> Result<(), CBox<str>> where P: AsRef<Path> {
+        let path = match path.as_ref().to_str() {
+            Some(path) => path,
+            None => return Err("path is not valid unicode".into())
+        };
+        util::with_cstr(path, |c_path| unsafe {
+            let mut error = mem::MaybeUninit::uninit();
+            if target_machine::LLVMTargetMachineEmitToFile(self.into(), module.into(), c_path as *mut c_char, file_type.into(), error.as_mut_ptr()) == 1 {
+                Err(CBox::new(error.assume_init()))
+            } else {
+                Ok(())
+            }
+        })
+    }
+}
@@ -1,10 +1,11 @@
 use ffi::prelude::LLVMTypeRef;
 use ffi::{core, target, LLVMTypeKind};
-use libc::{c_int, c_uint};
+use libc::{c_char, c_int, c_uint};
 use compile::Compile;
 use context::{Context, GetContext};
 use target::TargetData;
 use util::{self, Sub};
+use value::Value;
 use std::{fmt, mem};
 use std::marker::PhantomData;
 use std::iter::Iterator;
@@ -24,6 +25,56 @@ deref!{$ty, Type}
     )
 }
 
+/// The different kinds of type that LLVM supports.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum TypeKind {
+    /// A type with no size.
+    Void = 0,
+    /// A 16-bit floating-point type.
+    Half = 1,
+    /// A 32-bit floating-point type.
+    Float = 2,
+    /// A 64-bit floating-point type.
+    Double = 3,
+    /// An 80-bit floating-point type used on X87.
+    X86FP80 = 4,
+    /// A 128-bit floating-point type with a 112-bit mantissa.
+    FP128 = 5,
+    /// A 128-bit floating-point type made of two 64-bit types.
+    PPCFP128 = 6,
+    /// A label that can be branched to.
+    Label = 7,
+    /// An integer type of any width.
+    Integer = 8,
+    /// A function signature type.
+    Function = 9,
+    /// A structure type.
+    Struct = 10,
+    /// An array type.
+    Array = 11,
+    /// A pointer type.
+    Pointer = 12,
+    /// A SIMD vector type.
+    Vector = 13,
+    /// A metadata type.
+    Metadata = 14,
+    /// An MMX vector type used on X86.
+    X86MMX = 15,
+    /// A token type, which can't be stored or inspected.
+    Token = 16
+}
+impl From<LLVMTypeKind> for TypeKind {
+    fn from(kind: LLVMTypeKind) -> TypeKind {
+        unsafe { mem::transmute(kind) }
+    }
+}
+impl From<TypeKind> for LLVMTypeKind {
+    fn from(kind: TypeKind) -> LLVMTypeKind {
+        unsafe { mem::transmute(kind) }
+    }
+}
+
 /// Defines how a value should be laid out in memory.
 pub struct Type(PhantomData<[u8]>);
 native_ref!{&Type = LLVMTypeRef}
@@ -35,6 +86,102 @@ impl Type {
     pub fn get<'a, T>(context:&'a Context) -> &'a Type where T:Compile<'a> {
         T::get_type(context)
     }
+    /// Get the integer type with the number of bits given in the context given.
+    ///
+    /// Any width that LLVM supports can be used, such as `i3` or `i128`.
+    pub fn get_int(context: &Context, bits: usize) -> &Type {
+        unsafe { core::LLVMIntTypeInContext(context.into(), bits as c_uint) }.into()
+    }
+    /// Get the `i1` type in the context given.
+    pub fn get_i1(context: &Context) -> &Type {
+        unsafe { core::LLVMInt1TypeInContext(context.into()) }.into()
+    }
+    /// Get the `i8` type in the context given.
+    pub fn get_i8(context: &Context) -> &Type {
+        unsafe { core::LLVMInt8TypeInContext(context.into()) }.into()
+    }
+    /// Get the `i16` type in the context given.
+    pub fn get_i16(context: &Context) -> &Type {
+        unsafe { core::LLVMInt16TypeInContext(context.into()) }.into()
+    }
+    /// Get the `i32` type in the context given.
+    pub fn get_i32(context: &Context) -> &Type {
+        unsafe { core::LLVMInt32TypeInContext(context.into()) }.into()
+    }
+    /// Get the `i64` type in the context given.
+    pub fn get_i64(context: &Context) -> &Type {
+        unsafe { core::LLVMInt64TypeInContext(context.into()) }.into()
+    }
+    /// Get the 16-bit floating-point type in the context given.
+    pub fn get_half(context: &Context) -> &Type {
+        unsafe { core::LLVMHalfTypeInContext(context.into()) }.into()
+    }
+    /// Get the 32-bit floating-point type in the context given.
+    pub fn get_float(context: &Context) -> &Type {
+        unsafe { core::LLVMFloatTypeInContext(context.into()) }.into()
+    }
+    /// Get the 64-bit floating-point type in the context given.
+    pub fn get_double(context: &Context) -> &Type {
+        unsafe { core::LLVMDoubleTypeInContext(context.into()) }.into()
+    }
+    /// Get the 128-bit floating-point type in the context given.
+    pub fn get_fp128(context: &Context) -> &Type {
+        unsafe { core::LLVMFP128TypeInContext(context.into()) }.into()
+    }
+    /// Get the void type in the context given.
+    pub fn get_void(context: &Context) -> &Type {
+        unsafe { core::LLVMVoidTypeInContext(context.into()) }.into()
+    }
+    /// Get the type of a pointer to `elem` in the address space given.
+    pub fn get_pointer(elem: &Type, address_space: u32) -> &Type {
+        unsafe { core::LLVMPointerType(elem.into(), address_space as c_uint) }.into()
+    }
+    /// Returns the type of a pointer to this type in the default address space.
+    pub fn pointer_to(&self) -> &Type {
+        Type::get_pointer(self, 0)
+    }
+    /// Get the type of an array of `length` elements of type `elem`.
+    ///
+    /// This is equivalent to `ArrayType::new`.
+    pub fn get_array(elem: &Type, length: usize) -> &Type {
+        ArrayType::new(elem, length)
+    }
+    /// Get the type of a vector of `length` elements of type `elem`.
+    ///
+    /// This is equivalent to `VectorType::new`.
+    pub fn get_vector(elem: &Type, length: usize) -> &Type {
+        VectorType::new(elem, length)
+    }
+    /// Returns the element type of this pointer, array or vector type, or `None` if it is
+    /// none of those.
+    pub fn get_element_type(&self) -> Option<&Type> {
+        if self.is_pointer() || ArrayType::is(self) || VectorType::is(self) {
+            Some(unsafe { core::LLVMGetElementType(self.into()) }.into())
+        } else {
+            None
+        }
+    }
+    /// Returns the number of elements in this array or vector type, or `None` if it is
+    /// neither of those.
+    pub fn get_length(&self) -> Option<usize> {
+        if let Some(array) = ArrayType::from_super(self) {
+            Some(array.get_length())
+        } else if let Some(vector) = VectorType::from_super(self) {
+            Some(vector.get_size())
+        } else {
+            None
+        }
+    }
+    /// Returns how long an integer of this type is in bits, or `None` if this isn't an integer type.
+    pub fn get_int_width(&self) -> Option<usize> {
+        IntegerType::from_super(self).map(|ty| ty.get_width())
+    }
+    /// Returns the kind of type this is.
+    ///
+    /// Since types are unique to each context, two types can be compared with `==` instead.
+    pub fn get_kind(&self) -> TypeKind {
+        unsafe { core::LLVMGetTypeKind(self.into()) }.into()
+    }
     /// Returns true if the size of the type is known at compile-time.
     ///
     /// This is equivalent to the type implementing `Sized` in Rust
@@ -79,10 +226,29 @@ impl Type {
         kind == LLVMTypeKind::LLVMFloatTypeKind as c_uint ||
         kind == LLVMTypeKind::LLVMDoubleTypeKind as c_uint
     }
+    /// Returns true if this type is any floating-point type, including the extended
+    /// precision types that `is_float` doesn't cover.
+    pub fn is_floating_point(&self) -> bool {
+        let kind = unsafe { core::LLVMGetTypeKind(self.into()) } as c_uint;
+        kind == LLVMTypeKind::LLVMHalfTypeKind as c_uint ||
+        kind == LLVMTypeKind::LLVMFloatTypeKind as c_uint ||
+        kind == LLVMTypeKind::LLVMDoubleTypeKind as c_uint ||
+        kind == LLVMTypeKind::LLVMX86_FP80TypeKind as c_uint ||
+        kind == LLVMTypeKind::LLVMFP128TypeKind as c_uint ||
+        kind == LLVMTypeKind::LLVMPPC_FP128TypeKind as c_uint
+    }
     /// Returns the size of the type in bytes.
     pub fn get_size(&self, target: &TargetData) -> usize {
         unsafe { target::LLVMABISizeOfType(target.into(), self.into()) as usize }
     }
+    /// Returns the size of the type in bits.
+    pub fn size_in_bits(&self, target: &TargetData) -> u64 {
+        target.size_of_in_bits(self)
+    }
+    /// Returns the alignment the ABI requires for this type, in bytes.
+    pub fn abi_alignment(&self, target: &TargetData) -> u32 {
+        unsafe { target::LLVMABIAlignmentOfType(target.into(), self.into()) as u32 }
+    }
 }
 
 /// A structure type, such as a tuple or struct.
@@ -104,6 +270,43 @@ impl StructType {
             ty.into()
         })
     }
+    /// Make a new named struct with no body, which can be set later with `set_body`.
+    ///
+    /// This is how recursive types such as linked list nodes can be made.
+    pub fn new_opaque<'a>(context: &'a Context, name: &str) -> &'a StructType {
+        util::with_cstr(name, |name| unsafe {
+            core::LLVMStructCreateNamed(context.into(), name).into()
+        })
+    }
+    /// Set the fields and packed representation of this struct.
+    pub fn set_body(&self, fields: &[&Type], packed: bool) {
+        unsafe { core::LLVMStructSetBody(self.into(), fields.as_ptr() as *mut LLVMTypeRef, fields.len() as c_uint, packed as c_int) }
+    }
+    /// Returns true if this struct has no body.
+    pub fn is_opaque(&self) -> bool {
+        unsafe { core::LLVMIsOpaqueStruct(self.into()) != 0 }
+    }
+    /// Returns true if this struct is packed.
+    pub fn is_packed(&self) -> bool {
+        unsafe { core::LLVMIsPackedStruct(self.into()) != 0 }
+    }
+    /// Returns the name of this struct, or `None` if it is a literal struct.
+    pub fn get_name(&self) -> Option<&str> {
+        unsafe { util::to_null_str(core::LLVMGetStructName(self.into()) as *mut c_char) }
+    }
+    /// Returns the offset in bytes of the element at `index` in this struct, with the layout given.
+    pub fn element_offset(&self, target: &TargetData, index: usize) -> u64 {
+        let count = unsafe { core::LLVMCountStructElementTypes(self.into()) } as usize;
+        assert!(index < count, "no such element {} on {:?}", index, self);
+        target.offset_of(self, index)
+    }
+    /// Returns the index of the element in this struct that contains the byte at `offset`,
+    /// with the layout given.
+    pub fn element_at_offset(&self, target: &TargetData, offset: u64) -> usize {
+        let size = target.size_of(self);
+        assert!(offset < size, "offset {} is outside {:?}, which is {} bytes", offset, self, size);
+        target.element_at(self, offset)
+    }
     /// Returns the elements that make up this struct.
     pub fn get_elements(&self) -> Vec<&Type> {
         unsafe {
@@ -134,8 +337,14 @@ unsafe impl Sub<Type> for FunctionType {
 }
 impl FunctionType {
     /// Make a new function signature with the return type and arguments given.
-    pub fn new<'a>(ret: &'a Type, args: &[&'a Type]) -> &'a FunctionType {
-        unsafe { core::LLVMFunctionType(ret.into(), args.as_ptr() as *mut LLVMTypeRef, args.len() as c_uint, 0) }.into()
+    ///
+    /// If `is_var_arg` is true, the function can take more arguments after those given.
+    pub fn new<'a>(ret: &'a Type, args: &[&'a Type], is_var_arg: bool) -> &'a FunctionType {
+        unsafe { core::LLVMFunctionType(ret.into(), args.as_ptr() as *mut LLVMTypeRef, args.len() as c_uint, is_var_arg as c_int) }.into()
+    }
+    /// Returns true if this signature takes a variable number of arguments.
+    pub fn is_var_arg(&self) -> bool {
+        unsafe { core::LLVMIsFunctionVarArg(self.into()) != 0 }
     }
     /// Returns the number of parameters this signature takes.
     pub fn num_params(&self) -> usize {
@@ -154,6 +363,16 @@ impl FunctionType {
     pub fn get_return(&self) -> &Type {
         unsafe { core::LLVMGetReturnType(self.into()).into() }
     }
+    /// Make an inline assembly snippet with this signature, which can be called with
+    /// `Builder::build_value_call`.
+    ///
+    /// The constraints list how the arguments and result map onto registers and what
+    /// the snippet clobbers, such as `~{memory}`.
+    pub fn inline_asm(&self, asm: &str, constraints: &str, has_side_effects: bool, is_align_stack: bool) -> &Value {
+        util::with_cstr(asm, |asm| util::with_cstr(constraints, |constraints| unsafe {
+            core::LLVMConstInlineAsm(self.into(), asm, constraints, has_side_effects as c_int, is_align_stack as c_int).into()
+        }))
+    }
 }
 
 /// A pointer type.
@@ -171,6 +390,10 @@ impl PointerType {
     pub fn get_element(&self) -> &Type {
         unsafe { mem::transmute(core::LLVMGetElementType(self.into())) }
     }
+    /// Returns the address space this pointer type points into.
+    pub fn get_address_space(&self) -> u32 {
+        unsafe { core::LLVMGetPointerAddressSpace(self.into()) as u32 }
+    }
 }
 
 /// An integer type.
@@ -178,7 +401,7 @@ pub struct IntegerType(PhantomData<[u8]>);
 native_ref!{&IntegerType = LLVMTypeRef}
 get_context!{IntegerType, LLVMGetTypeContext}
 to_str!{IntegerType, LLVMPrintTypeToString}
-sub!{IntegerType, LLVMPointerTypeKind}
+sub!{IntegerType, LLVMIntegerTypeKind}
 impl IntegerType {
     /// Make a new integer type that will be the size of the given number of bits.
     pub fn new(context: &Context, numbits: usize) -> &IntegerType {
@@ -198,7 +421,10 @@ to_str!{VectorType, LLVMPrintTypeToString}
 sub!{VectorType, LLVMVectorTypeKind}
 impl VectorType {
     /// Make a new vector type with the length given.
+    ///
+    /// Unlike arrays, vectors can't be empty so `length` must not be zero.
     pub fn new(element: &Type, length: usize) -> &VectorType {
+        assert!(length > 0, "cannot make a vector type with no elements");
         unsafe { core::LLVMVectorType(element.into(), length as c_uint) }.into()
     }
     /// Returns the element type of this vector type.
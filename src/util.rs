@@ -1,4 +1,5 @@
 use libc::c_char;
+use cbox::CBox;
 use std::ffi::{CStr, CString};
 use std::mem;
 use std::str;
@@ -19,12 +20,20 @@ pub unsafe trait Sub<T>: Sized {
         unsafe { mem::transmute(self) }
     }
 }
+/// An empty C string, passed as the name of values that shouldn't be named.
+pub static NULL_NAME:[c_char; 1] = [0];
+
 #[inline(always)]
 pub fn with_cstr<C, R>(text: &str, cb: C) -> R where C:FnOnce(*const c_char) -> R {
-    let c_text = CString::new(text).unwrap();
+    let c_text = to_cstring(text).unwrap_or_else(|err| panic!("{}", &*err));
     cb(c_text.as_bytes().as_ptr() as *const c_char)
 }
 
+/// Convert `text` to a C string, or return an error if it contains a nul byte.
+pub fn to_cstring(text: &str) -> Result<CString, CBox<str>> {
+    CString::new(text).map_err(|_| CBox::from(&*format!("{:?} contains a nul byte", text)))
+}
+
 #[inline(always)]
 pub unsafe fn to_str<'a>(text: *mut c_char) -> &'a str {
     let c_str = CStr::from_ptr(text);
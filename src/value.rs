@@ -1,14 +1,15 @@
-use libc::{c_char, c_uint, c_int};
-use ffi::prelude::LLVMValueRef;
-use ffi::{core, LLVMAttribute};
-use ffi::LLVMLinkage;
-use std::ffi::CString;
-use std::{fmt, mem};
+use libc::{c_char, c_uint, c_int, c_ulonglong, size_t};
+use ffi::prelude::{LLVMAttributeRef, LLVMBasicBlockRef, LLVMUseRef, LLVMValueRef};
+use ffi::{analysis, core, LLVMAttribute};
+use ffi::analysis::LLVMVerifierFailureAction;
+use ffi::{LLVMAtomicOrdering, LLVMAtomicRMWBinOp, LLVMIntPredicate, LLVMLinkage, LLVMOpcode, LLVMRealPredicate, LLVMValueKind, LLVMVisibility};
+use cbox::CBox;
+use std::{fmt, mem, slice, str};
 use std::ops::{Deref, Index};
 use std::marker::PhantomData;
 use block::{BasicBlock, BlockIter};
 use context::{Context, GetContext};
-use types::{FunctionType, Type};
+use types::{FunctionType, PointerType, Type};
 use util::{self, Sub};
 
 macro_rules! sub {
@@ -35,6 +36,21 @@ impl Deref for $this {
     )
 }
 
+macro_rules! const_op {
+    ($name:ident, $func:ident) => (
+        pub fn $name<'a>(left: &'a Value, right: &'a Value) -> &'a Value {
+            check_constants(&[left, right]);
+            unsafe { core::$func(left.into(), right.into()).into() }
+        }
+    )
+}
+
+fn check_constants(vals: &[&Value]) {
+    for val in vals {
+        assert!(val.is_constant(), "{:?} is not a constant", val);
+    }
+}
+
 /// A typed value that can be used as an operand in instructions.
 pub struct Value(PhantomData<[u8]>);
 native_ref!(&Value = LLVMValueRef);
@@ -44,22 +60,133 @@ impl Value {
     pub fn new_struct<'a>(context: &'a Context, vals: &[&'a Value], packed: bool) -> &'a Value {
         unsafe { core::LLVMConstStructInContext(context.into(), vals.as_ptr() as *mut LLVMValueRef, vals.len() as c_uint, packed as c_int) }.into()
     }
+    /// Create a new constant array with the element type and values given.
+    ///
+    /// Every value must have the type `elem`.
+    pub fn new_array<'a>(elem: &'a Type, vals: &[&'a Value]) -> &'a Value {
+        check_elements(elem, vals);
+        unsafe { core::LLVMConstArray(elem.into(), vals.as_ptr() as *mut LLVMValueRef, vals.len() as c_uint).into() }
+    }
     /// Create a new constant vector from the values given.
+    ///
+    /// The element type is taken from the first value, so `vals` must not be empty.
     pub fn new_vector<'a>(vals: &[&'a Value]) -> &'a Value {
+        assert!(!vals.is_empty(), "cannot create a vector with no elements");
+        check_elements(vals[0].get_type(), vals);
         unsafe { core::LLVMConstVector(vals.as_ptr() as *mut LLVMValueRef, vals.len() as c_uint).into() }
     }
-    /// Create a new constant C string from the text given.
-    pub fn new_string<'a>(context: &'a Context, text: &str, rust_style: bool) -> &'a Value {
+    /// Create a new constant character array from the text given.
+    ///
+    /// If `null_terminated` is true, a null byte will be appended to the array.
+    /// The text is passed through as raw bytes, so embedded null bytes are kept.
+    pub fn new_string<'a>(context: &'a Context, text: &str, null_terminated: bool) -> &'a Value {
         unsafe {
             let ptr = text.as_ptr() as *const c_char;
             let len = text.len() as c_uint;
-            core::LLVMConstStringInContext(context.into(), ptr, len, rust_style as c_int).into()
+            core::LLVMConstStringInContext(context.into(), ptr, len, !null_terminated as c_int).into()
         }
     }
+    /// Create a new constant integer of the given integer type.
+    ///
+    /// If `sign_extend` is true, `val` is treated as signed when the type is
+    /// wider than 64 bits.
+    pub fn new_int<'a>(ty: &'a Type, val: u64, sign_extend: bool) -> &'a Value {
+        assert!(ty.is_integer(), "expected integer type, got {:?}", ty);
+        unsafe { core::LLVMConstInt(ty.into(), val as c_ulonglong, sign_extend as c_int) }.into()
+    }
+    /// Create a new constant `i1` in the context given.
+    pub fn new_bool<'a>(context: &'a Context, val: bool) -> &'a Value {
+        let ty = unsafe { core::LLVMInt1TypeInContext(context.into()) }.into();
+        Value::new_int(ty, val as u64, false)
+    }
+    /// Create a new constant floating-point number of the given floating-point type.
+    pub fn new_real<'a>(ty: &'a Type, val: f64) -> &'a Value {
+        assert!(ty.is_floating_point(), "expected floating-point type, got {:?}", ty);
+        unsafe { core::LLVMConstReal(ty.into(), val) }.into()
+    }
+    /// Create a new constant floating-point number of the given floating-point type
+    /// by parsing `text`.
+    ///
+    /// This avoids the rounding that happens when going through an `f64` first.
+    pub fn new_real_from_str<'a>(ty: &'a Type, text: &str) -> &'a Value {
+        assert!(ty.is_floating_point(), "expected floating-point type, got {:?}", ty);
+        util::with_cstr(text, |ptr| unsafe {
+            core::LLVMConstRealOfString(ty.into(), ptr).into()
+        })
+    }
+    /// Make a new metadata string containing the text given.
+    pub fn new_metadata_string<'a>(context: &'a Context, text: &str) -> &'a Value {
+        unsafe { core::LLVMMDStringInContext(context.into(), text.as_ptr() as *const c_char, text.len() as c_uint).into() }
+    }
+    /// Make a new metadata node with the values given as its operands.
+    pub fn new_metadata_node<'a>(context: &'a Context, vals: &[&'a Value]) -> &'a Value {
+        unsafe { core::LLVMMDNodeInContext(context.into(), vals.as_ptr() as *mut LLVMValueRef, vals.len() as c_uint).into() }
+    }
     /// Create a new constant undefined value of the given type.
     pub fn new_undef<'a>(ty: &'a Type) -> &'a Value {
         unsafe { core::LLVMGetUndef(ty.into()).into() }
     }
+    /// Create a new constant null value of the given type.
+    pub fn new_null<'a>(ty: &'a Type) -> &'a Value {
+        unsafe { core::LLVMConstNull(ty.into()).into() }
+    }
+    /// Create a new constant of the given type with every bit cleared.
+    ///
+    /// This is the same as `new_null`, and for structs, arrays and vectors it makes a
+    /// single `zeroinitializer` instead of a zero for each element.
+    pub fn const_zero<'a>(ty: &'a Type) -> &'a Value {
+        Value::new_null(ty)
+    }
+    /// Create a new constant of the given integer or vector type with every bit set.
+    pub fn new_all_ones<'a>(ty: &'a Type) -> &'a Value {
+        unsafe { core::LLVMConstAllOnes(ty.into()).into() }
+    }
+    const_op!{const_add, LLVMConstAdd}
+    const_op!{const_sub, LLVMConstSub}
+    const_op!{const_mul, LLVMConstMul}
+    const_op!{const_sdiv, LLVMConstSDiv}
+    const_op!{const_udiv, LLVMConstUDiv}
+    const_op!{const_and, LLVMConstAnd}
+    const_op!{const_or, LLVMConstOr}
+    const_op!{const_xor, LLVMConstXor}
+    const_op!{const_shl, LLVMConstShl}
+    const_op!{const_fadd, LLVMConstFAdd}
+    const_op!{const_fsub, LLVMConstFSub}
+    const_op!{const_fmul, LLVMConstFMul}
+    const_op!{const_fdiv, LLVMConstFDiv}
+    /// Make a constant expression that negates the constant integer given.
+    pub fn const_neg(val: &Value) -> &Value {
+        check_constants(&[val]);
+        unsafe { core::LLVMConstNeg(val.into()).into() }
+    }
+    /// Make a constant expression that inverts the bits of the constant integer given.
+    pub fn const_not(val: &Value) -> &Value {
+        check_constants(&[val]);
+        unsafe { core::LLVMConstNot(val.into()).into() }
+    }
+    /// Make a constant expression that reinterprets the constant given as the type `dest`.
+    pub fn const_bitcast<'a>(val: &'a Value, dest: &'a Type) -> &'a Value {
+        check_constants(&[val]);
+        unsafe { core::LLVMConstBitCast(val.into(), dest.into()).into() }
+    }
+    /// Make a constant expression that converts the constant pointer given to the integer type `dest`.
+    pub fn const_ptr_to_int<'a>(ptr: &'a Value, dest: &'a Type) -> &'a Value {
+        check_constants(&[ptr]);
+        assert!(ptr.get_type().is_pointer() && dest.is_integer(), "cannot convert {:?} to {:?}", ptr.get_type(), dest);
+        unsafe { core::LLVMConstPtrToInt(ptr.into(), dest.into()).into() }
+    }
+    /// Make a constant expression that converts the constant integer given to the pointer type `dest`.
+    pub fn const_int_to_ptr<'a>(int: &'a Value, dest: &'a Type) -> &'a Value {
+        check_constants(&[int]);
+        assert!(int.get_type().is_integer() && dest.is_pointer(), "cannot convert {:?} to {:?}", int.get_type(), dest);
+        unsafe { core::LLVMConstIntToPtr(int.into(), dest.into()).into() }
+    }
+    /// Make a constant expression that computes the address of a subelement of the constant pointer given.
+    pub fn const_gep<'a>(ptr: &'a Value, indices: &[&'a Value]) -> &'a Value {
+        check_constants(&[ptr]);
+        check_constants(indices);
+        unsafe { core::LLVMConstGEP(ptr.into(), indices.as_ptr() as *mut LLVMValueRef, indices.len() as c_uint).into() }
+    }
     /// Returns the name of this value, or `None` if it lacks a name
     pub fn get_name(&self) -> Option<&str> {
         unsafe {
@@ -69,15 +196,640 @@ impl Value {
     }
     /// Sets the name of this value
     pub fn set_name(&self, name: &str) {
-        let c_name = CString::new(name).unwrap();
+        self.try_set_name(name).unwrap_or_else(|err| panic!("{}", &*err))
+    }
+    /// Sets the name of this value, or returns an error if the name contains a nul byte.
+    ///
+    /// This is the non-panicking version of `set_name`.
+    pub fn try_set_name(&self, name: &str) -> Result<(), CBox<str>> {
+        let c_name = util::to_cstring(name)?;
         unsafe {
             core::LLVMSetValueName(self.into(), c_name.as_ptr())
         }
+        Ok(())
+    }
+    /// Returns the kind of this value.
+    pub fn get_kind(&self) -> ValueKind {
+        unsafe { core::LLVMGetValueKind(self.into()).into() }
     }
     /// Returns the type of this value
     pub fn get_type(&self) -> &Type {
         unsafe { core::LLVMTypeOf(self.into()) }.into()
     }
+    /// Returns the number of operands this value has, such as the two values added by an `add`.
+    pub fn get_num_operands(&self) -> usize {
+        unsafe { core::LLVMGetNumOperands(self.into()) as usize }
+    }
+    /// Returns the operand at the index given, or `None` if there is no such operand.
+    pub fn get_operand(&self, index: usize) -> Option<&Value> {
+        if index < self.get_num_operands() {
+            unsafe { util::ptr_to_null(core::LLVMGetOperand(self.into(), index as c_uint)) }
+        } else {
+            None
+        }
+    }
+    /// Replace the operand at the index given with `val`.
+    pub fn set_operand(&self, index: usize, val: &Value) {
+        let count = self.get_num_operands();
+        assert!(index < count, "no such operand {} on {:?}, which has {} operands", index, self, count);
+        unsafe { core::LLVMSetOperand(self.into(), index as c_uint, val.into()) }
+    }
+    /// Iterate through the uses of this value.
+    pub fn uses(&self) -> Uses {
+        Uses {
+            use_: unsafe { core::LLVMGetFirstUse(self.into()) },
+            marker: PhantomData
+        }
+    }
+    /// Replace every use of this value with `new`.
+    ///
+    /// Both values must have the same type.
+    pub fn replace_all_uses_with(&self, new: &Value) {
+        let (old_ty, new_ty) = (self.get_type(), new.get_type());
+        assert!(old_ty == new_ty, "cannot replace {:?} with {:?}", old_ty, new_ty);
+        unsafe { core::LLVMReplaceAllUsesWith(self.into(), new.into()) }
+    }
+    /// Returns the value of this constant integer zero-extended to 64 bits, or `None` if
+    /// this isn't a constant integer or is wider than 64 bits.
+    pub fn get_zext_value(&self) -> Option<u64> {
+        if self.is_constant_int_64() {
+            Some(unsafe { core::LLVMConstIntGetZExtValue(self.into()) as u64 })
+        } else {
+            None
+        }
+    }
+    /// Returns the value of this constant integer sign-extended to 64 bits, or `None` if
+    /// this isn't a constant integer or is wider than 64 bits.
+    pub fn get_sext_value(&self) -> Option<i64> {
+        if self.is_constant_int_64() {
+            Some(unsafe { core::LLVMConstIntGetSExtValue(self.into()) as i64 })
+        } else {
+            None
+        }
+    }
+    /// Returns the value of this constant floating-point value as a double and whether
+    /// converting it to a double lost information, or `None` if this isn't a constant
+    /// floating-point value.
+    pub fn get_double_value(&self) -> Option<(f64, bool)> {
+        if unsafe { core::LLVMIsAConstantFP(self.into()).is_null() } {
+            None
+        } else {
+            let mut loses_info = 0;
+            let value = unsafe { core::LLVMConstRealGetDouble(self.into(), &mut loses_info) };
+            Some((value, loses_info != 0))
+        }
+    }
+    fn is_constant_int_64(&self) -> bool {
+        unsafe { !core::LLVMIsAConstantInt(self.into()).is_null() } && self.get_type().get_int_width() <= Some(64)
+    }
+    /// Returns true if this value is a compile-time constant.
+    pub fn is_constant(&self) -> bool {
+        unsafe { core::LLVMIsConstant(self.into()) != 0 }
+    }
+    /// Returns true if this value is undefined.
+    pub fn is_undef(&self) -> bool {
+        unsafe { core::LLVMIsUndef(self.into()) != 0 }
+    }
+    /// Returns true if this value is a constant null value.
+    pub fn is_null(&self) -> bool {
+        unsafe { core::LLVMIsNull(self.into()) != 0 }
+    }
+    /// Returns the operation this instruction performs, or `None` if this isn't an instruction
+    /// or its opcode isn't covered by `Opcode`.
+    pub fn get_instruction_opcode(&self) -> Option<Opcode> {
+        if self.is_instruction() {
+            Opcode::from_llvm(unsafe { core::LLVMGetInstructionOpcode(self.into()) })
+        } else {
+            None
+        }
+    }
+    /// Attach the metadata node given to this instruction, replacing any node of
+    /// the same kind it already has.
+    ///
+    /// The kind can be found from its name with `Context::get_metadata_kind_id`.
+    pub fn set_metadata(&self, kind_id: u32, node: &Value) {
+        assert!(self.is_instruction(), "cannot attach metadata to {:?}", self);
+        unsafe { core::LLVMSetMetadata(self.into(), kind_id as c_uint, node.into()) }
+    }
+    /// Returns the metadata node of the kind given attached to this instruction, or
+    /// `None` if it doesn't have one.
+    pub fn get_metadata(&self, kind_id: u32) -> Option<&Value> {
+        assert!(self.is_instruction(), "{:?} cannot have metadata", self);
+        unsafe { util::ptr_to_null(core::LLVMGetMetadata(self.into(), kind_id as c_uint)) }
+    }
+    fn is_instruction(&self) -> bool {
+        unsafe { !core::LLVMIsAInstruction(self.into()).is_null() }
+    }
+    /// Set the alignment in bytes of this alloca, load, store or global.
+    ///
+    /// The alignment must be a power of two.
+    pub fn set_alignment(&self, bytes: u32) {
+        assert!(bytes.is_power_of_two(), "alignment {} is not a power of two", bytes);
+        assert!(self.has_alignment(), "{:?} does not have an alignment", self);
+        unsafe { core::LLVMSetAlignment(self.into(), bytes as c_uint) }
+    }
+    /// Returns the alignment in bytes of this alloca, load, store or global, or
+    /// zero if it hasn't been set.
+    pub fn get_alignment(&self) -> u32 {
+        assert!(self.has_alignment(), "{:?} does not have an alignment", self);
+        unsafe { core::LLVMGetAlignment(self.into()) as u32 }
+    }
+    /// Make this load or store atomic, with the ordering given.
+    pub fn set_ordering(&self, ordering: AtomicOrdering) {
+        assert!(self.is_load_or_store(), "{:?} is not a load or store", self);
+        unsafe { core::LLVMSetOrdering(self.into(), ordering.into()) }
+    }
+    /// Returns the ordering of this load or store, which is `NotAtomic` unless it has been set.
+    pub fn get_ordering(&self) -> AtomicOrdering {
+        assert!(self.is_load_or_store(), "{:?} is not a load or store", self);
+        unsafe { core::LLVMGetOrdering(self.into()).into() }
+    }
+    fn is_load_or_store(&self) -> bool {
+        unsafe {
+            !core::LLVMIsALoadInst(self.into()).is_null() ||
+            !core::LLVMIsAStoreInst(self.into()).is_null()
+        }
+    }
+    fn has_alignment(&self) -> bool {
+        unsafe {
+            !core::LLVMIsAAllocaInst(self.into()).is_null() ||
+            !core::LLVMIsALoadInst(self.into()).is_null() ||
+            !core::LLVMIsAStoreInst(self.into()).is_null() ||
+            !core::LLVMIsAGlobalValue(self.into()).is_null()
+        }
+    }
+}
+
+/// An edge between a value and one of the values that uses it.
+pub struct Use(PhantomData<[u8]>);
+native_ref!(&Use = LLVMUseRef);
+impl Use {
+    /// Returns the value that is using the used value, such as an instruction.
+    pub fn get_user(&self) -> &Value {
+        unsafe { core::LLVMGetUser(self.into()) }.into()
+    }
+    /// Returns the value that is being used.
+    pub fn get_used_value(&self) -> &Value {
+        unsafe { core::LLVMGetUsedValue(self.into()) }.into()
+    }
+}
+
+#[derive(Copy, Clone)]
+/// An iterator through the uses of a value.
+pub struct Uses<'a> {
+    use_: LLVMUseRef,
+    marker: PhantomData<&'a ()>
+}
+impl<'a> Iterator for Uses<'a> {
+    type Item = &'a Use;
+    fn next(&mut self) -> Option<&'a Use> {
+        if self.use_.is_null() {
+            None
+        } else {
+            let use_ = self.use_;
+            self.use_ = unsafe { core::LLVMGetNextUse(use_) };
+            Some(use_.into())
+        }
+    }
+}
+
+fn check_elements(elem: &Type, vals: &[&Value]) {
+    for (index, val) in vals.iter().enumerate() {
+        let ty = val.get_type();
+        if ty != elem {
+            panic!("element {} has type {:?}, expected {:?}", index, ty, elem)
+        }
+    }
+}
+
+/// The kind of a value, which can be used to tell what it is.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ValueKind {
+    /// An argument to a function.
+    Argument,
+    /// A basic block.
+    BasicBlock,
+    /// A use of memory in memory SSA.
+    MemoryUse,
+    /// A definition of memory in memory SSA.
+    MemoryDef,
+    /// A PHI node of memory in memory SSA.
+    MemoryPhi,
+    /// A function.
+    Function,
+    /// An alias of a global.
+    GlobalAlias,
+    /// An indirect function, which is resolved when the program is loaded.
+    GlobalIFunc,
+    /// A global variable.
+    GlobalVariable,
+    /// The address of a basic block.
+    BlockAddress,
+    /// A constant expression.
+    ConstantExpr,
+    /// A constant array.
+    ConstantArray,
+    /// A constant struct.
+    ConstantStruct,
+    /// A constant vector.
+    ConstantVector,
+    /// An undefined value.
+    UndefValue,
+    /// A zero initializer of an aggregate.
+    ConstantAggregateZero,
+    /// A constant array of simple elements, such as a string.
+    ConstantDataArray,
+    /// A constant vector of simple elements.
+    ConstantDataVector,
+    /// A constant integer.
+    ConstantInt,
+    /// A constant floating-point value.
+    ConstantFP,
+    /// A null pointer.
+    ConstantPointerNull,
+    /// The empty token.
+    ConstantTokenNone,
+    /// Metadata used as a value.
+    MetadataAsValue,
+    /// An inline assembly snippet.
+    InlineAsm,
+    /// An instruction.
+    Instruction,
+}
+impl From<LLVMValueKind> for ValueKind {
+    fn from(kind: LLVMValueKind) -> ValueKind {
+        match kind {
+            LLVMValueKind::LLVMArgumentValueKind => ValueKind::Argument,
+            LLVMValueKind::LLVMBasicBlockValueKind => ValueKind::BasicBlock,
+            LLVMValueKind::LLVMMemoryUseValueKind => ValueKind::MemoryUse,
+            LLVMValueKind::LLVMMemoryDefValueKind => ValueKind::MemoryDef,
+            LLVMValueKind::LLVMMemoryPhiValueKind => ValueKind::MemoryPhi,
+            LLVMValueKind::LLVMFunctionValueKind => ValueKind::Function,
+            LLVMValueKind::LLVMGlobalAliasValueKind => ValueKind::GlobalAlias,
+            LLVMValueKind::LLVMGlobalIFuncValueKind => ValueKind::GlobalIFunc,
+            LLVMValueKind::LLVMGlobalVariableValueKind => ValueKind::GlobalVariable,
+            LLVMValueKind::LLVMBlockAddressValueKind => ValueKind::BlockAddress,
+            LLVMValueKind::LLVMConstantExprValueKind => ValueKind::ConstantExpr,
+            LLVMValueKind::LLVMConstantArrayValueKind => ValueKind::ConstantArray,
+            LLVMValueKind::LLVMConstantStructValueKind => ValueKind::ConstantStruct,
+            LLVMValueKind::LLVMConstantVectorValueKind => ValueKind::ConstantVector,
+            LLVMValueKind::LLVMUndefValueValueKind => ValueKind::UndefValue,
+            LLVMValueKind::LLVMConstantAggregateZeroValueKind => ValueKind::ConstantAggregateZero,
+            LLVMValueKind::LLVMConstantDataArrayValueKind => ValueKind::ConstantDataArray,
+            LLVMValueKind::LLVMConstantDataVectorValueKind => ValueKind::ConstantDataVector,
+            LLVMValueKind::LLVMConstantIntValueKind => ValueKind::ConstantInt,
+            LLVMValueKind::LLVMConstantFPValueKind => ValueKind::ConstantFP,
+            LLVMValueKind::LLVMConstantPointerNullValueKind => ValueKind::ConstantPointerNull,
+            LLVMValueKind::LLVMConstantTokenNoneValueKind => ValueKind::ConstantTokenNone,
+            LLVMValueKind::LLVMMetadataAsValueValueKind => ValueKind::MetadataAsValue,
+            LLVMValueKind::LLVMInlineAsmValueKind => ValueKind::InlineAsm,
+            LLVMValueKind::LLVMInstructionValueKind => ValueKind::Instruction
+        }
+    }
+}
+
+/// The operation an instruction performs.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Opcode {
+    /// Return from a function.
+    Ret,
+    /// Branch to another block, possibly conditionally.
+    Br,
+    /// Branch to one of several blocks depending on an integer.
+    Switch,
+    /// Branch to an address.
+    IndirectBr,
+    /// Call a function that may unwind.
+    Invoke,
+    /// Mark code that can't be reached.
+    Unreachable,
+    /// Integer addition.
+    Add,
+    /// Floating-point addition.
+    FAdd,
+    /// Integer subtraction.
+    Sub,
+    /// Floating-point subtraction.
+    FSub,
+    /// Integer multiplication.
+    Mul,
+    /// Floating-point multiplication.
+    FMul,
+    /// Unsigned integer division.
+    UDiv,
+    /// Signed integer division.
+    SDiv,
+    /// Floating-point division.
+    FDiv,
+    /// Unsigned integer remainder.
+    URem,
+    /// Signed integer remainder.
+    SRem,
+    /// Floating-point remainder.
+    FRem,
+    /// Shift left.
+    Shl,
+    /// Logical shift right.
+    LShr,
+    /// Arithmetic shift right.
+    AShr,
+    /// Bitwise and.
+    And,
+    /// Bitwise or.
+    Or,
+    /// Bitwise xor.
+    Xor,
+    /// Allocate memory on the stack.
+    Alloca,
+    /// Load from memory.
+    Load,
+    /// Store to memory.
+    Store,
+    /// Compute the address of a subelement.
+    GetElementPtr,
+    /// Truncate an integer.
+    Trunc,
+    /// Zero-extend an integer.
+    ZExt,
+    /// Sign-extend an integer.
+    SExt,
+    /// Convert a floating-point value to an unsigned integer.
+    FPToUI,
+    /// Convert a floating-point value to a signed integer.
+    FPToSI,
+    /// Convert an unsigned integer to a floating-point value.
+    UIToFP,
+    /// Convert a signed integer to a floating-point value.
+    SIToFP,
+    /// Truncate a floating-point value.
+    FPTrunc,
+    /// Extend a floating-point value.
+    FPExt,
+    /// Convert a pointer to an integer.
+    PtrToInt,
+    /// Convert an integer to a pointer.
+    IntToPtr,
+    /// Reinterpret a value as another type.
+    BitCast,
+    /// Compare integers.
+    ICmp,
+    /// Compare floating-point values.
+    FCmp,
+    /// Pick a value depending on the predecessor block.
+    PHI,
+    /// Call a function.
+    Call,
+    /// Pick one of two values depending on a condition.
+    Select,
+    /// Reserved for use by passes.
+    UserOp1,
+    /// Reserved for use by passes.
+    UserOp2,
+    /// Read a variable argument.
+    VAArg,
+    /// Extract an element from a vector.
+    ExtractElement,
+    /// Insert an element into a vector.
+    InsertElement,
+    /// Shuffle the elements of two vectors.
+    ShuffleVector,
+    /// Extract a field from an aggregate.
+    ExtractValue,
+    /// Insert a field into an aggregate.
+    InsertValue,
+    /// Order memory operations.
+    Fence,
+    /// Atomically compare and exchange a value in memory.
+    AtomicCmpXchg,
+    /// Atomically modify a value in memory.
+    AtomicRMW,
+    /// Resume propagation of an exception.
+    Resume,
+    /// Catch an exception.
+    LandingPad,
+    /// Convert a pointer to another address space.
+    AddrSpaceCast,
+    /// Return from a cleanup pad.
+    CleanupRet,
+    /// Return from a catch pad.
+    CatchRet,
+    /// Handle an exception in a catch switch.
+    CatchPad,
+    /// Run cleanup code while unwinding.
+    CleanupPad,
+    /// Pick a catch pad while unwinding.
+    CatchSwitch,
+}
+impl Opcode {
+    /// Returns the opcode matching the one given, or `None` if it isn't covered by this enum.
+    #[allow(unreachable_patterns)]
+    fn from_llvm(opcode: LLVMOpcode) -> Option<Opcode> {
+        Some(match opcode {
+            LLVMOpcode::LLVMRet => Opcode::Ret,
+            LLVMOpcode::LLVMBr => Opcode::Br,
+            LLVMOpcode::LLVMSwitch => Opcode::Switch,
+            LLVMOpcode::LLVMIndirectBr => Opcode::IndirectBr,
+            LLVMOpcode::LLVMInvoke => Opcode::Invoke,
+            LLVMOpcode::LLVMUnreachable => Opcode::Unreachable,
+            LLVMOpcode::LLVMAdd => Opcode::Add,
+            LLVMOpcode::LLVMFAdd => Opcode::FAdd,
+            LLVMOpcode::LLVMSub => Opcode::Sub,
+            LLVMOpcode::LLVMFSub => Opcode::FSub,
+            LLVMOpcode::LLVMMul => Opcode::Mul,
+            LLVMOpcode::LLVMFMul => Opcode::FMul,
+            LLVMOpcode::LLVMUDiv => Opcode::UDiv,
+            LLVMOpcode::LLVMSDiv => Opcode::SDiv,
+            LLVMOpcode::LLVMFDiv => Opcode::FDiv,
+            LLVMOpcode::LLVMURem => Opcode::URem,
+            LLVMOpcode::LLVMSRem => Opcode::SRem,
+            LLVMOpcode::LLVMFRem => Opcode::FRem,
+            LLVMOpcode::LLVMShl => Opcode::Shl,
+            LLVMOpcode::LLVMLShr => Opcode::LShr,
+            LLVMOpcode::LLVMAShr => Opcode::AShr,
+            LLVMOpcode::LLVMAnd => Opcode::And,
+            LLVMOpcode::LLVMOr => Opcode::Or,
+            LLVMOpcode::LLVMXor => Opcode::Xor,
+            LLVMOpcode::LLVMAlloca => Opcode::Alloca,
+            LLVMOpcode::LLVMLoad => Opcode::Load,
+            LLVMOpcode::LLVMStore => Opcode::Store,
+            LLVMOpcode::LLVMGetElementPtr => Opcode::GetElementPtr,
+            LLVMOpcode::LLVMTrunc => Opcode::Trunc,
+            LLVMOpcode::LLVMZExt => Opcode::ZExt,
+            LLVMOpcode::LLVMSExt => Opcode::SExt,
+            LLVMOpcode::LLVMFPToUI => Opcode::FPToUI,
+            LLVMOpcode::LLVMFPToSI => Opcode::FPToSI,
+            LLVMOpcode::LLVMUIToFP => Opcode::UIToFP,
+            LLVMOpcode::LLVMSIToFP => Opcode::SIToFP,
+            LLVMOpcode::LLVMFPTrunc => Opcode::FPTrunc,
+            LLVMOpcode::LLVMFPExt => Opcode::FPExt,
+            LLVMOpcode::LLVMPtrToInt => Opcode::PtrToInt,
+            LLVMOpcode::LLVMIntToPtr => Opcode::IntToPtr,
+            LLVMOpcode::LLVMBitCast => Opcode::BitCast,
+            LLVMOpcode::LLVMICmp => Opcode::ICmp,
+            LLVMOpcode::LLVMFCmp => Opcode::FCmp,
+            LLVMOpcode::LLVMPHI => Opcode::PHI,
+            LLVMOpcode::LLVMCall => Opcode::Call,
+            LLVMOpcode::LLVMSelect => Opcode::Select,
+            LLVMOpcode::LLVMUserOp1 => Opcode::UserOp1,
+            LLVMOpcode::LLVMUserOp2 => Opcode::UserOp2,
+            LLVMOpcode::LLVMVAArg => Opcode::VAArg,
+            LLVMOpcode::LLVMExtractElement => Opcode::ExtractElement,
+            LLVMOpcode::LLVMInsertElement => Opcode::InsertElement,
+            LLVMOpcode::LLVMShuffleVector => Opcode::ShuffleVector,
+            LLVMOpcode::LLVMExtractValue => Opcode::ExtractValue,
+            LLVMOpcode::LLVMInsertValue => Opcode::InsertValue,
+            LLVMOpcode::LLVMFence => Opcode::Fence,
+            LLVMOpcode::LLVMAtomicCmpXchg => Opcode::AtomicCmpXchg,
+            LLVMOpcode::LLVMAtomicRMW => Opcode::AtomicRMW,
+            LLVMOpcode::LLVMResume => Opcode::Resume,
+            LLVMOpcode::LLVMLandingPad => Opcode::LandingPad,
+            LLVMOpcode::LLVMAddrSpaceCast => Opcode::AddrSpaceCast,
+            LLVMOpcode::LLVMCleanupRet => Opcode::CleanupRet,
+            LLVMOpcode::LLVMCatchRet => Opcode::CatchRet,
+            LLVMOpcode::LLVMCatchPad => Opcode::CatchPad,
+            LLVMOpcode::LLVMCleanupPad => Opcode::CleanupPad,
+            LLVMOpcode::LLVMCatchSwitch => Opcode::CatchSwitch,
+            _ => return None
+        })
+    }
+}
+impl From<Opcode> for LLVMOpcode {
+    fn from(opcode: Opcode) -> LLVMOpcode {
+        match opcode {
+            Opcode::Ret => LLVMOpcode::LLVMRet,
+            Opcode::Br => LLVMOpcode::LLVMBr,
+            Opcode::Switch => LLVMOpcode::LLVMSwitch,
+            Opcode::IndirectBr => LLVMOpcode::LLVMIndirectBr,
+            Opcode::Invoke => LLVMOpcode::LLVMInvoke,
+            Opcode::Unreachable => LLVMOpcode::LLVMUnreachable,
+            Opcode::Add => LLVMOpcode::LLVMAdd,
+            Opcode::FAdd => LLVMOpcode::LLVMFAdd,
+            Opcode::Sub => LLVMOpcode::LLVMSub,
+            Opcode::FSub => LLVMOpcode::LLVMFSub,
+            Opcode::Mul => LLVMOpcode::LLVMMul,
+            Opcode::FMul => LLVMOpcode::LLVMFMul,
+            Opcode::UDiv => LLVMOpcode::LLVMUDiv,
+            Opcode::SDiv => LLVMOpcode::LLVMSDiv,
+            Opcode::FDiv => LLVMOpcode::LLVMFDiv,
+            Opcode::URem => LLVMOpcode::LLVMURem,
+            Opcode::SRem => LLVMOpcode::LLVMSRem,
+            Opcode::FRem => LLVMOpcode::LLVMFRem,
+            Opcode::Shl => LLVMOpcode::LLVMShl,
+            Opcode::LShr => LLVMOpcode::LLVMLShr,
+            Opcode::AShr => LLVMOpcode::LLVMAShr,
+            Opcode::And => LLVMOpcode::LLVMAnd,
+            Opcode::Or => LLVMOpcode::LLVMOr,
+            Opcode::Xor => LLVMOpcode::LLVMXor,
+            Opcode::Alloca => LLVMOpcode::LLVMAlloca,
+            Opcode::Load => LLVMOpcode::LLVMLoad,
+            Opcode::Store => LLVMOpcode::LLVMStore,
+            Opcode::GetElementPtr => LLVMOpcode::LLVMGetElementPtr,
+            Opcode::Trunc => LLVMOpcode::LLVMTrunc,
+            Opcode::ZExt => LLVMOpcode::LLVMZExt,
+            Opcode::SExt => LLVMOpcode::LLVMSExt,
+            Opcode::FPToUI => LLVMOpcode::LLVMFPToUI,
+            Opcode::FPToSI => LLVMOpcode::LLVMFPToSI,
+            Opcode::UIToFP => LLVMOpcode::LLVMUIToFP,
+            Opcode::SIToFP => LLVMOpcode::LLVMSIToFP,
+            Opcode::FPTrunc => LLVMOpcode::LLVMFPTrunc,
+            Opcode::FPExt => LLVMOpcode::LLVMFPExt,
+            Opcode::PtrToInt => LLVMOpcode::LLVMPtrToInt,
+            Opcode::IntToPtr => LLVMOpcode::LLVMIntToPtr,
+            Opcode::BitCast => LLVMOpcode::LLVMBitCast,
+            Opcode::ICmp => LLVMOpcode::LLVMICmp,
+            Opcode::FCmp => LLVMOpcode::LLVMFCmp,
+            Opcode::PHI => LLVMOpcode::LLVMPHI,
+            Opcode::Call => LLVMOpcode::LLVMCall,
+            Opcode::Select => LLVMOpcode::LLVMSelect,
+            Opcode::UserOp1 => LLVMOpcode::LLVMUserOp1,
+            Opcode::UserOp2 => LLVMOpcode::LLVMUserOp2,
+            Opcode::VAArg => LLVMOpcode::LLVMVAArg,
+            Opcode::ExtractElement => LLVMOpcode::LLVMExtractElement,
+            Opcode::InsertElement => LLVMOpcode::LLVMInsertElement,
+            Opcode::ShuffleVector => LLVMOpcode::LLVMShuffleVector,
+            Opcode::ExtractValue => LLVMOpcode::LLVMExtractValue,
+            Opcode::InsertValue => LLVMOpcode::LLVMInsertValue,
+            Opcode::Fence => LLVMOpcode::LLVMFence,
+            Opcode::AtomicCmpXchg => LLVMOpcode::LLVMAtomicCmpXchg,
+            Opcode::AtomicRMW => LLVMOpcode::LLVMAtomicRMW,
+            Opcode::Resume => LLVMOpcode::LLVMResume,
+            Opcode::LandingPad => LLVMOpcode::LLVMLandingPad,
+            Opcode::AddrSpaceCast => LLVMOpcode::LLVMAddrSpaceCast,
+            Opcode::CleanupRet => LLVMOpcode::LLVMCleanupRet,
+            Opcode::CatchRet => LLVMOpcode::LLVMCatchRet,
+            Opcode::CatchPad => LLVMOpcode::LLVMCatchPad,
+            Opcode::CleanupPad => LLVMOpcode::LLVMCleanupPad,
+            Opcode::CatchSwitch => LLVMOpcode::LLVMCatchSwitch
+        }
+    }
+}
+
+/// The guarantees an atomic operation gives about how it is ordered with other memory operations.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum AtomicOrdering {
+    /// The operation isn't atomic.
+    NotAtomic = 0,
+    /// The operation is atomic, but doesn't synchronise with anything.
+    Unordered = 1,
+    /// There is a single total order of the operations on each address.
+    Monotonic = 2,
+    /// Later memory operations can't be moved before this one.
+    Acquire   = 4,
+    /// Earlier memory operations can't be moved after this one.
+    Release   = 5,
+    /// Both acquire and release.
+    AcqRel    = 6,
+    /// Acquire and release, with a single total order of all sequentially consistent operations.
+    SeqCst    = 7,
+}
+impl From<LLVMAtomicOrdering> for AtomicOrdering {
+    fn from(ordering: LLVMAtomicOrdering) -> AtomicOrdering {
+        unsafe { mem::transmute(ordering) }
+    }
+}
+impl From<AtomicOrdering> for LLVMAtomicOrdering {
+    fn from(ordering: AtomicOrdering) -> LLVMAtomicOrdering {
+        unsafe { mem::transmute(ordering) }
+    }
+}
+
+/// The operation an atomic read-modify-write performs on the value in memory.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum AtomicRMWBinOp {
+    /// Replace the old value with the new one.
+    Xchg = 0,
+    /// Add the value to the old value.
+    Add  = 1,
+    /// Subtract the value from the old value.
+    Sub  = 2,
+    /// Bitwise and the value with the old value.
+    And  = 3,
+    /// Bitwise and the value with the old value, then invert the result.
+    Nand = 4,
+    /// Bitwise or the value with the old value.
+    Or   = 5,
+    /// Bitwise xor the value with the old value.
+    Xor  = 6,
+    /// Take the larger of the values, comparing them as signed integers.
+    Max  = 7,
+    /// Take the smaller of the values, comparing them as signed integers.
+    Min  = 8,
+    /// Take the larger of the values, comparing them as unsigned integers.
+    UMax = 9,
+    /// Take the smaller of the values, comparing them as unsigned integers.
+    UMin = 10,
+}
+impl From<LLVMAtomicRMWBinOp> for AtomicRMWBinOp {
+    fn from(op: LLVMAtomicRMWBinOp) -> AtomicRMWBinOp {
+        unsafe { mem::transmute(op) }
+    }
+}
+impl From<AtomicRMWBinOp> for LLVMAtomicRMWBinOp {
+    fn from(op: AtomicRMWBinOp) -> LLVMAtomicRMWBinOp {
+        unsafe { mem::transmute(op) }
+    }
 }
 
 /// Comparative operations on values.
@@ -90,6 +842,91 @@ pub enum Predicate {
     LessThan,
     LessThanOrEqual
 }
+/// Comparative operations on integers, with explicit signedness.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum IntPredicate {
+    /// Equal.
+    Eq  = 32,
+    /// Not equal.
+    Ne  = 33,
+    /// Unsigned greater than.
+    Ugt = 34,
+    /// Unsigned greater than or equal.
+    Uge = 35,
+    /// Unsigned less than.
+    Ult = 36,
+    /// Unsigned less than or equal.
+    Ule = 37,
+    /// Signed greater than.
+    Sgt = 38,
+    /// Signed greater than or equal.
+    Sge = 39,
+    /// Signed less than.
+    Slt = 40,
+    /// Signed less than or equal.
+    Sle = 41
+}
+impl From<LLVMIntPredicate> for IntPredicate {
+    fn from(pred: LLVMIntPredicate) -> IntPredicate {
+        unsafe { mem::transmute(pred) }
+    }
+}
+impl From<IntPredicate> for LLVMIntPredicate {
+    fn from(pred: IntPredicate) -> LLVMIntPredicate {
+        unsafe { mem::transmute(pred) }
+    }
+}
+/// Comparative operations on floating-point numbers.
+///
+/// The ordered predicates are false if either operand is NaN, while the unordered
+/// predicates are true if either operand is NaN.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum RealPredicate {
+    /// Always false.
+    PredicateFalse = 0,
+    /// Ordered and equal.
+    Oeq = 1,
+    /// Ordered and greater than.
+    Ogt = 2,
+    /// Ordered and greater than or equal.
+    Oge = 3,
+    /// Ordered and less than.
+    Olt = 4,
+    /// Ordered and less than or equal.
+    Ole = 5,
+    /// Ordered and not equal.
+    One = 6,
+    /// Ordered (neither operand is NaN).
+    Ord = 7,
+    /// Unordered (either operand is NaN).
+    Uno = 8,
+    /// Unordered or equal.
+    Ueq = 9,
+    /// Unordered or greater than.
+    Ugt = 10,
+    /// Unordered or greater than or equal.
+    Uge = 11,
+    /// Unordered or less than.
+    Ult = 12,
+    /// Unordered or less than or equal.
+    Ule = 13,
+    /// Unordered or not equal.
+    Une = 14,
+    /// Always true.
+    PredicateTrue = 15
+}
+impl From<LLVMRealPredicate> for RealPredicate {
+    fn from(pred: LLVMRealPredicate) -> RealPredicate {
+        unsafe { mem::transmute(pred) }
+    }
+}
+impl From<RealPredicate> for LLVMRealPredicate {
+    fn from(pred: RealPredicate) -> LLVMRealPredicate {
+        unsafe { mem::transmute(pred) }
+    }
+}
 /// An argument that is passed to a function.
 pub struct Arg(PhantomData<[u8]>);
 native_ref!(&Arg = LLVMValueRef);
@@ -134,6 +971,100 @@ impl Arg {
     }
 }
 
+/// A PHI node, which selects a value depending on which block was run before it.
+pub struct PhiNode(PhantomData<[u8]>);
+native_ref!(&PhiNode = LLVMValueRef);
+sub!{PhiNode, LLVMIsAPHINode}
+to_str!{PhiNode, LLVMPrintValueToString}
+impl PhiNode {
+    /// Add incoming values to this node, each paired with the block it comes from.
+    ///
+    /// This can be called multiple times to add more incoming edges.
+    pub fn add_incoming(&self, incoming: &[(&Value, &BasicBlock)]) {
+        let ty = self.get_type();
+        let mut values: Vec<LLVMValueRef> = Vec::with_capacity(incoming.len());
+        let mut blocks: Vec<LLVMBasicBlockRef> = Vec::with_capacity(incoming.len());
+        for &(value, block) in incoming {
+            assert!(value.get_type() == ty, "expected incoming value of type {:?}, got {:?}", ty, value.get_type());
+            values.push(value.into());
+            blocks.push(block.into());
+        }
+        unsafe { core::LLVMAddIncoming(self.into(), values.as_mut_ptr(), blocks.as_mut_ptr(), incoming.len() as c_uint) }
+    }
+    /// Returns the number of incoming edges this node has.
+    pub fn count_incoming(&self) -> usize {
+        unsafe { core::LLVMCountIncoming(self.into()) as usize }
+    }
+}
+
+/// An instruction that branches to one of several blocks depending on an integer value.
+pub struct Switch(PhantomData<[u8]>);
+native_ref!(&Switch = LLVMValueRef);
+sub!{Switch, LLVMIsASwitchInst}
+
+impl Switch {
+    /// Returns the value being switched on.
+    pub fn get_condition(&self) -> &Value {
+        unsafe { core::LLVMGetOperand(self.into(), 0).into() }
+    }
+    /// Add a case that branches to `dest` when the condition is equal to `on_val`.
+    ///
+    /// The value must be a constant integer with the same type as the condition.
+    pub fn add_case(&self, on_val: &Value, dest: &BasicBlock) {
+        let cond_ty = self.get_condition().get_type();
+        assert!(unsafe { !core::LLVMIsAConstantInt(on_val.into()).is_null() }, "switch case {:?} is not a constant integer", on_val);
+        assert!(on_val.get_type() == cond_ty, "switch case {:?} does not match condition type {:?}", on_val, cond_ty);
+        unsafe { core::LLVMAddCase(self.into(), on_val.into(), dest.into()) }
+    }
+}
+
+/// An instruction at the start of a block that an invoke unwinds to, which yields the exception being caught.
+pub struct LandingPad(PhantomData<[u8]>);
+native_ref!(&LandingPad = LLVMValueRef);
+sub!{LandingPad, LLVMIsALandingPadInst}
+
+impl LandingPad {
+    /// Add a clause to this landing pad, which is a constant catch type or filter array.
+    pub fn add_clause(&self, clause: &Value) {
+        assert!(clause.is_constant(), "landing pad clause {:?} is not a constant", clause);
+        unsafe { core::LLVMAddClause(self.into(), clause.into()) }
+    }
+    /// Set whether this landing pad is a cleanup, which is run even if none of its clauses match.
+    pub fn set_cleanup(&self, is_cleanup: bool) {
+        unsafe { core::LLVMSetCleanup(self.into(), is_cleanup as c_int) }
+    }
+}
+
+/// A call to a function.
+pub struct Call(PhantomData<[u8]>);
+native_ref!(&Call = LLVMValueRef);
+sub!{Call, LLVMIsACallInst}
+
+impl Call {
+    /// Returns the value being called.
+    pub fn get_called_value(&self) -> &Value {
+        unsafe {
+            let count = core::LLVMGetNumOperands(self.into());
+            core::LLVMGetOperand(self.into(), count as c_uint - 1).into()
+        }
+    }
+    /// Set the calling convention used by this call.
+    ///
+    /// This should match the calling convention of the function being called.
+    pub fn set_call_conv(&self, conv: CallConv) {
+        let callee = unsafe { core::LLVMIsAFunction(self.get_called_value().into()) };
+        if !callee.is_null() {
+            let func: &Function = callee.into();
+            debug_assert!(func.get_call_conv() == conv, "call uses {:?} but the function called uses {:?}", conv, func.get_call_conv());
+        }
+        unsafe { core::LLVMSetInstructionCallConv(self.into(), conv.into()) }
+    }
+    /// Returns the calling convention used by this call.
+    pub fn get_call_conv(&self) -> CallConv {
+        unsafe { core::LLVMGetInstructionCallConv(self.into()).into() }
+    }
+}
+
 /// A value with global scope (eg: Function, Alias, Global variable)
 pub struct GlobalValue(PhantomData<[u8]>);
 native_ref!(&GlobalValue = LLVMValueRef);
@@ -152,11 +1083,46 @@ impl GlobalValue {
             core::LLVMGetLinkage(self.into()).into()
         }
     }
+    /// Set the section this global will be placed in, or clear it if `name` is empty.
+    pub fn set_section(&self, name: &str) {
+        util::with_cstr(name, |ptr| unsafe {
+            core::LLVMSetSection(self.into(), ptr)
+        })
+    }
+    /// Returns the section this global will be placed in, or `None` if it hasn't been set.
+    pub fn get_section(&self) -> Option<&str> {
+        unsafe {
+            match util::to_null_str(core::LLVMGetSection(self.into()) as *mut c_char) {
+                Some("") => None,
+                section => section
+            }
+        }
+    }
+    /// Set whether the address of this global is insignificant, which lets identical
+    /// constants be merged.
+    pub fn set_unnamed_addr(&self, unnamed_addr: bool) {
+        unsafe { core::LLVMSetUnnamedAddr(self.into(), unnamed_addr as c_int) }
+    }
+    /// Returns true if the address of this global is insignificant.
+    pub fn has_unnamed_addr(&self) -> bool {
+        unsafe { core::LLVMHasUnnamedAddr(self.into()) != 0 }
+    }
+    /// Set the visibility of this global.
+    pub fn set_visibility(&self, visibility: Visibility) {
+        unsafe {
+            core::LLVMSetVisibility(self.into(), visibility.into());
+        }
+    }
+    /// Returns the visibility of this global.
+    pub fn get_visibility(&self) -> Visibility {
+        unsafe {
+            core::LLVMGetVisibility(self.into()).into()
+        }
+    }
     /// Returns true if this global is a declaration (as opposed to a definition).
     pub fn is_declaration(&self) -> bool {
         unsafe {
-            // FIXME: There should be a constant somewhere, instead of '1'
-            core::LLVMIsDeclaration(self.into()) == 1
+            core::LLVMIsDeclaration(self.into()) != 0
         }
     }
 }
@@ -168,12 +1134,17 @@ sub!{GlobalVariable, LLVMIsAGlobalVariable, GlobalValue}
 to_str!{GlobalVariable, LLVMPrintValueToString}
 impl GlobalVariable {
     /// Set the initial value of the global
+    ///
+    /// The value must have the same type as the global's contents.
     pub fn set_initializer(&self, val: &Value) {
+        let ty = self.get_type();
+        let elem = PointerType::from_super(ty).map(|ptr| ptr.get_element()).unwrap_or(ty);
+        assert!(val.get_type() == elem, "expected initializer of type {:?}, got {:?}", elem, val.get_type());
         unsafe {
             core::LLVMSetInitializer(self.into(), val.into())
         }
     }
-    /// Set the initial value of the global
+    /// Returns the initial value of the global, or `None` if it has none.
     pub fn get_initializer(&self) -> Option<&Value> {
         unsafe {
             util::ptr_to_null(core::LLVMGetInitializer(self.into()))
@@ -192,6 +1163,27 @@ impl GlobalVariable {
             core::LLVMIsGlobalConstant(self.into()) != 0
         }
     }
+    /// Set whether this global may be initialized outside of this module, so that its
+    /// initializer can't be assumed to be its value at the start of the program.
+    pub fn set_externally_initialized(&self, is_externally_initialized: bool) {
+        unsafe { core::LLVMSetExternallyInitialized(self.into(), is_externally_initialized as c_int) }
+    }
+    /// Returns true if this global may be initialized outside of this module.
+    pub fn is_externally_initialized(&self) -> bool {
+        unsafe { core::LLVMIsExternallyInitialized(self.into()) != 0 }
+    }
+    /// Set whether this global has a separate instance for each thread.
+    pub fn set_thread_local(&self, is_thread_local: bool) {
+        unsafe {
+            core::LLVMSetThreadLocal(self.into(), is_thread_local as c_int);
+        }
+    }
+    /// Returns true if this global has a separate instance for each thread.
+    pub fn is_thread_local(&self) -> bool {
+        unsafe {
+            core::LLVMIsThreadLocal(self.into()) != 0
+        }
+    }
 }
 
 /// An alias to another global value.
@@ -210,12 +1202,9 @@ to_str!{Function, LLVMPrintValueToString}
 impl Index<usize> for Function {
     type Output = Arg;
     fn index(&self, index: usize) -> &Arg {
-        unsafe {
-            if index < core::LLVMCountParams(self.into()) as usize {
-                core::LLVMGetParam(self.into(), index as c_uint).into()
-            } else {
-                panic!("no such index {} on {:?}", index, self.get_type())
-            }
+        match self.get_param(index) {
+            Some(param) => param,
+            None => panic!("no such index {} on {:?}", index, self.get_type())
         }
     }
 }
@@ -231,14 +1220,66 @@ impl Function {
             core::LLVMAppendBasicBlockInContext(self.get_context().into(), self.into(), ptr).into()
         })
     }
+    /// Insert a basic block with the name given before the block `before` and return it.
+    pub fn insert_block_before<'a>(&'a self, before: &'a BasicBlock, name: &str) -> &'a BasicBlock {
+        util::with_cstr(name, |ptr| unsafe {
+            core::LLVMInsertBasicBlockInContext(self.get_context().into(), before.into(), ptr).into()
+        })
+    }
     /// Iterate through this function's basic blocks.
     pub fn blocks(&self) -> BlockIter {
         BlockIter::new(self)
     }
+    /// Returns the number of basic blocks in this function.
+    pub fn get_block_count(&self) -> usize {
+        unsafe { core::LLVMCountBasicBlocks(self.into()) as usize }
+    }
+    /// Iterate through this function's parameters.
+    pub fn params(&self) -> Params {
+        Params {
+            value: unsafe { core::LLVMGetFirstParam(self.into()) },
+            marker: PhantomData
+        }
+    }
+    /// Returns the parameter at the index given, or `None` if there is no such parameter.
+    ///
+    /// This is the non-panicking version of indexing the function.
+    pub fn get_param(&self, index: usize) -> Option<&Arg> {
+        if index < self.get_param_count() {
+            Some(unsafe { core::LLVMGetParam(self.into(), index as c_uint).into() })
+        } else {
+            None
+        }
+    }
+    /// Returns the number of parameters this function takes.
+    pub fn get_param_count(&self) -> usize {
+        unsafe { core::LLVMCountParams(self.into()) as usize }
+    }
     /// Returns the entry block of this function or `None` if there is none.
     pub fn get_entry(&self) -> Option<&BasicBlock> {
         unsafe { mem::transmute(core::LLVMGetEntryBasicBlock(self.into())) }
     }
+    /// Check that this function is well-formed.
+    ///
+    /// LLVM doesn't describe the problem when verifying a single function, so the
+    /// error only names the function. Use `Module::verify` to get a description.
+    pub fn verify(&self) -> Result<(), CBox<str>> {
+        let action = LLVMVerifierFailureAction::LLVMReturnStatusAction;
+        if unsafe { analysis::LLVMVerifyFunction(self.into(), action) } == 0 {
+            Ok(())
+        } else {
+            let name = self.get_name().unwrap_or("<unnamed>");
+            Err(CBox::from(&*format!("function {} is broken", name)))
+        }
+    }
+    /// Remove this function from its module and delete it.
+    ///
+    /// This is unsafe because any other references to this function, including
+    /// calls to it, will be invalid afterwards, but this can't be guranteed using
+    /// Rust semantics.
+    pub unsafe fn delete(&self) {
+        core::LLVMDeleteFunction(self.into())
+    }
     /// Returns the function signature representing this function's signature.
     pub fn get_signature(&self) -> &FunctionType {
         unsafe {
@@ -246,6 +1287,65 @@ impl Function {
             core::LLVMGetElementType(ty).into()
         }
     }
+    /// Returns the identifier of the intrinsic this function declares, or `None` if it
+    /// isn't an intrinsic.
+    ///
+    /// Intrinsics such as `llvm.sqrt.f64` can be declared with `Module::get_intrinsic_declaration`.
+    pub fn get_intrinsic_id(&self) -> Option<u32> {
+        match unsafe { core::LLVMGetIntrinsicID(self.into()) } {
+            0 => None,
+            id => Some(id as u32)
+        }
+    }
+    /// Set the personality function this function uses to handle exceptions.
+    pub fn set_personality_function(&self, personality: &Function) {
+        unsafe { core::LLVMSetPersonalityFn(self.into(), personality.into()) }
+    }
+    /// Set the name of the garbage collection strategy used by this function.
+    pub fn set_gc(&self, name: &str) {
+        util::with_cstr(name, |ptr| unsafe {
+            core::LLVMSetGC(self.into(), ptr)
+        })
+    }
+    /// Returns the name of the garbage collection strategy used by this function, or
+    /// `None` if it doesn't use one.
+    pub fn get_gc(&self) -> Option<&str> {
+        unsafe { util::to_null_str(core::LLVMGetGC(self.into()) as *mut c_char) }
+    }
+    /// Set the calling convention used by this function.
+    pub fn set_call_conv(&self, conv: CallConv) {
+        unsafe { core::LLVMSetFunctionCallConv(self.into(), conv.into()) }
+    }
+    /// Returns the calling convention used by this function.
+    pub fn get_call_conv(&self) -> CallConv {
+        unsafe { core::LLVMGetFunctionCallConv(self.into()).into() }
+    }
+    /// Attach the attribute given to this function, its return value or one of its parameters.
+    pub fn add_enum_attribute(&self, index: AttributeIndex, attr: &EnumAttribute) {
+        unsafe { core::LLVMAddAttributeAtIndex(self.into(), index.into(), attr.into()) }
+    }
+    /// Returns the attribute of the kind given at the index given, or `None` if there is none.
+    pub fn get_enum_attribute(&self, index: AttributeIndex, kind: u32) -> Option<&EnumAttribute> {
+        unsafe { util::ptr_to_null(core::LLVMGetEnumAttributeAtIndex(self.into(), index.into(), kind as c_uint)) }
+    }
+    /// Remove the attribute of the kind given from the index given.
+    pub fn remove_enum_attribute(&self, index: AttributeIndex, kind: u32) {
+        unsafe { core::LLVMRemoveEnumAttributeAtIndex(self.into(), index.into(), kind as c_uint) }
+    }
+    /// Attach the string attribute given to this function, its return value or one of its parameters.
+    pub fn add_string_attribute(&self, index: AttributeIndex, attr: &StringAttribute) {
+        unsafe { core::LLVMAddAttributeAtIndex(self.into(), index.into(), attr.into()) }
+    }
+    /// Returns the string attribute with the key given at the index given, or `None` if there is none.
+    pub fn get_string_attribute(&self, index: AttributeIndex, key: &str) -> Option<&StringAttribute> {
+        unsafe {
+            util::ptr_to_null(core::LLVMGetStringAttributeAtIndex(self.into(), index.into(), key.as_ptr() as *const c_char, key.len() as c_uint))
+        }
+    }
+    /// Remove the string attribute with the key given from the index given.
+    pub fn remove_string_attribute(&self, index: AttributeIndex, key: &str) {
+        unsafe { core::LLVMRemoveStringAttributeAtIndex(self.into(), index.into(), key.as_ptr() as *const c_char, key.len() as c_uint) }
+    }
     /// Add the attribute given to this function.
     pub fn add_attribute(&self, attr: Attribute) {
         unsafe { core::LLVMAddFunctionAttr(self.into(), attr.into()) }
@@ -283,12 +1383,33 @@ impl Function {
         unsafe { core::LLVMRemoveFunctionAttr(self.into(), attr.into()) }
     }
 }
+#[derive(Copy, Clone)]
+/// An iterator through the parameters of a function.
+pub struct Params<'a> {
+    value: LLVMValueRef,
+    marker: PhantomData<&'a ()>
+}
+impl<'a> Iterator for Params<'a> {
+    type Item = &'a Arg;
+    fn next(&mut self) -> Option<&'a Arg> {
+        if self.value.is_null() {
+            None
+        } else {
+            let param = self.value;
+            self.value = unsafe { core::LLVMGetNextParam(param) };
+            Some(param.into())
+        }
+    }
+}
 impl GetContext for Function {
     fn get_context(&self) -> &Context {
         self.get_type().get_context()
     }
 }
 /// These indicate how you want arguments / functions to be handled.
+///
+/// This is the legacy bitmask representation of attributes, which newer
+/// versions of LLVM have removed. Prefer `EnumAttribute` for new code.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 #[repr(C)]
 pub enum Attribute {
@@ -356,6 +1477,109 @@ impl From<Attribute> for LLVMAttribute {
     }
 }
 
+/// An attribute identified by its kind, such as `noinline` or `align`, with an optional integer value.
+pub struct EnumAttribute(PhantomData<[u8]>);
+native_ref!(&EnumAttribute = LLVMAttributeRef);
+impl EnumAttribute {
+    /// Create an attribute from the name of its kind, as it appears in textual IR, and its value.
+    ///
+    /// The value is zero for attributes that don't take a value.
+    pub fn new<'a>(context: &'a Context, name: &str, value: u64) -> &'a EnumAttribute {
+        EnumAttribute::try_new(context, name, value).unwrap_or_else(|err| panic!("{}", &*err))
+    }
+    /// Create an attribute from the name of its kind and its value, or return an error if
+    /// there is no attribute with that name.
+    ///
+    /// This is the non-panicking version of `EnumAttribute::new`.
+    pub fn try_new<'a>(context: &'a Context, name: &str, value: u64) -> Result<&'a EnumAttribute, CBox<str>> {
+        match EnumAttribute::get_kind_for_name(name) {
+            Some(kind) => Ok(unsafe { core::LLVMCreateEnumAttribute(context.into(), kind as c_uint, value).into() }),
+            None => Err(CBox::from(&*format!("unknown attribute {}", name)))
+        }
+    }
+    /// Returns the kind identifier for the attribute name given, or `None` if there is no such attribute.
+    pub fn get_kind_for_name(name: &str) -> Option<u32> {
+        let kind = unsafe { core::LLVMGetEnumAttributeKindForName(name.as_ptr() as *const c_char, name.len() as size_t) };
+        if kind == 0 {
+            None
+        } else {
+            Some(kind as u32)
+        }
+    }
+    /// Returns the kind identifier of this attribute.
+    pub fn get_kind(&self) -> u32 {
+        unsafe { core::LLVMGetEnumAttributeKind(self.into()) as u32 }
+    }
+    /// Returns the value of this attribute.
+    pub fn get_value(&self) -> u64 {
+        unsafe { core::LLVMGetEnumAttributeValue(self.into()) }
+    }
+}
+
+/// An attribute made of a key and a value, such as `"target-cpu"="skylake"`.
+pub struct StringAttribute(PhantomData<[u8]>);
+native_ref!(&StringAttribute = LLVMAttributeRef);
+impl StringAttribute {
+    /// Create an attribute with the key and value given.
+    pub fn new<'a>(context: &'a Context, key: &str, value: &str) -> &'a StringAttribute {
+        unsafe {
+            core::LLVMCreateStringAttribute(context.into(),
+                key.as_ptr() as *const c_char, key.len() as c_uint,
+                value.as_ptr() as *const c_char, value.len() as c_uint).into()
+        }
+    }
+    /// Returns the key of this attribute, or `None` if it isn't valid UTF-8.
+    pub fn get_key(&self) -> Option<&str> {
+        str::from_utf8(self.get_key_bytes()).ok()
+    }
+    /// Returns the bytes of the key of this attribute.
+    pub fn get_key_bytes(&self) -> &[u8] {
+        unsafe {
+            let mut len = 0;
+            let ptr = core::LLVMGetStringAttributeKind(self.into(), &mut len);
+            slice::from_raw_parts(ptr as *const u8, len as usize)
+        }
+    }
+    /// Returns the value of this attribute, or `None` if it isn't valid UTF-8.
+    pub fn get_value(&self) -> Option<&str> {
+        str::from_utf8(self.get_value_bytes()).ok()
+    }
+    /// Returns the bytes of the value of this attribute.
+    pub fn get_value_bytes(&self) -> &[u8] {
+        unsafe {
+            let mut len = 0;
+            let ptr = core::LLVMGetStringAttributeValue(self.into(), &mut len);
+            slice::from_raw_parts(ptr as *const u8, len as usize)
+        }
+    }
+}
+
+/// The place in a function that an attribute is attached to.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum AttributeIndex {
+    /// The return value of the function.
+    Return,
+    /// The function itself.
+    Function,
+    /// The parameter with the index given, starting from zero.
+    ///
+    /// The index must be less than `u32::MAX - 1`, since LLVM uses the values above that for
+    /// the function itself.
+    Param(u32)
+}
+impl From<AttributeIndex> for c_uint {
+    fn from(index: AttributeIndex) -> c_uint {
+        match index {
+            AttributeIndex::Return => 0,
+            AttributeIndex::Function => !0,
+            AttributeIndex::Param(index) => {
+                assert!(index < !0 - 1, "parameter index {} out of range", index);
+                index + 1
+            }
+        }
+    }
+}
+
 /// A way of indicating to LLVM how you want a global to interact during linkage.
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 #[repr(C)]
@@ -394,6 +1618,77 @@ impl From<Linkage> for LLVMLinkage {
     }
 }
 
+/// How a global is exposed to other modules once it has been linked.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub enum Visibility {
+    /// The global is visible to other modules, and can be overridden in a shared object.
+    Default   = 0,
+    /// The global is not placed in the dynamic symbol table, so it can't be referenced outside the shared object it is in.
+    Hidden    = 1,
+    /// The global is visible to other modules, but references inside its own shared object will always resolve to it.
+    Protected = 2,
+}
+impl From<LLVMVisibility> for Visibility {
+    fn from(visibility: LLVMVisibility) -> Visibility {
+        unsafe { mem::transmute(visibility) }
+    }
+}
+impl From<Visibility> for LLVMVisibility {
+    fn from(visibility: Visibility) -> LLVMVisibility {
+        unsafe { mem::transmute(visibility) }
+    }
+}
+
+/// The calling convention of a function or call, which determines how arguments and return values are passed.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum CallConv {
+    /// The default calling convention, which matches the C ABI of the target.
+    C,
+    /// Makes calls as fast as possible, which may involve passing arguments in registers. Calls must match the function's definition exactly.
+    Fast,
+    /// Makes calls as cheap as possible for the caller, assuming the call is not commonly executed.
+    Cold,
+    /// Used by the WebKit JavaScript compiler.
+    WebKitJS,
+    /// Used for dynamic register allocation by patchpoints.
+    AnyReg,
+    /// The stdcall convention on 32-bit X86, where the callee cleans up the stack.
+    X86Stdcall,
+    /// The fastcall convention on 32-bit X86, which passes the first two arguments in registers.
+    X86Fastcall,
+    /// Any other calling convention, identified by its numeric LLVM ID.
+    Other(u32)
+}
+impl From<c_uint> for CallConv {
+    fn from(conv: c_uint) -> CallConv {
+        match conv {
+            0 => CallConv::C,
+            8 => CallConv::Fast,
+            9 => CallConv::Cold,
+            12 => CallConv::WebKitJS,
+            13 => CallConv::AnyReg,
+            64 => CallConv::X86Stdcall,
+            65 => CallConv::X86Fastcall,
+            other => CallConv::Other(other as u32)
+        }
+    }
+}
+impl From<CallConv> for c_uint {
+    fn from(conv: CallConv) -> c_uint {
+        match conv {
+            CallConv::C => 0,
+            CallConv::Fast => 8,
+            CallConv::Cold => 9,
+            CallConv::WebKitJS => 12,
+            CallConv::AnyReg => 13,
+            CallConv::X86Stdcall => 64,
+            CallConv::X86Fastcall => 65,
+            CallConv::Other(other) => other as c_uint
+        }
+    }
+}
+
 impl GetContext for Value {
     fn get_context(&self) -> &Context {
         self.get_type().get_context()
@@ -27,3 +27,51 @@ fn test_argument_attributes() {
     assert!(!x.has_attribute(ByVal));
     assert!(x.has_attribute(InReg));
 }
+
+#[test]
+fn test_enum_attributes() {
+    let ctx = Context::new();
+    let module = Module::new("simple", &ctx);
+    let func = module.add_function("main", Type::get::<fn(i32) -> i32>(&ctx));
+    let kind = EnumAttribute::get_kind_for_name("noinline").unwrap();
+    assert!(EnumAttribute::get_kind_for_name("nonsense").is_none());
+    assert!(func.get_enum_attribute(AttributeIndex::Function, kind).is_none());
+    let noinline = EnumAttribute::new(&ctx, "noinline", 0);
+    assert!(EnumAttribute::try_new(&ctx, "nonsense", 0).is_err());
+    assert_eq!(EnumAttribute::try_new(&ctx, "noinline", 0).unwrap().get_kind(), kind);
+    assert_eq!(noinline.get_kind(), kind);
+    func.add_enum_attribute(AttributeIndex::Function, noinline);
+    func.add_enum_attribute(AttributeIndex::Param(0), EnumAttribute::new(&ctx, "zeroext", 0));
+    assert!(func.get_enum_attribute(AttributeIndex::Function, kind).is_some());
+    let text = format!("{:?}", module);
+    assert!(text.contains("noinline"));
+    assert!(text.contains("i32 zeroext"));
+    func.remove_enum_attribute(AttributeIndex::Function, kind);
+    assert!(func.get_enum_attribute(AttributeIndex::Function, kind).is_none());
+}
+
+#[test]
+fn test_string_attributes() {
+    let ctx = Context::new();
+    let module = Module::new("simple", &ctx);
+    let func = module.add_function("main", Type::get::<fn() -> ()>(&ctx));
+    assert!(func.get_string_attribute(AttributeIndex::Function, "no-nans-fp-math").is_none());
+    let attr = StringAttribute::new(&ctx, "no-nans-fp-math", "true");
+    assert_eq!(attr.get_key(), Some("no-nans-fp-math"));
+    func.add_string_attribute(AttributeIndex::Function, attr);
+    let found = func.get_string_attribute(AttributeIndex::Function, "no-nans-fp-math").unwrap();
+    assert_eq!(found.get_value(), Some("true"));
+    assert_eq!(found.get_value_bytes(), b"true");
+    assert!(format!("{:?}", module).contains("\"no-nans-fp-math\"=\"true\""));
+    func.remove_string_attribute(AttributeIndex::Function, "no-nans-fp-math");
+    assert!(func.get_string_attribute(AttributeIndex::Function, "no-nans-fp-math").is_none());
+}
+
+#[test]
+#[should_panic(expected = "out of range")]
+fn test_attribute_param_out_of_range() {
+    let ctx = Context::new();
+    let module = Module::new("simple", &ctx);
+    let func = module.add_function("main", Type::get::<fn(i32) -> i32>(&ctx));
+    func.get_enum_attribute(AttributeIndex::Param(u32::max_value()), EnumAttribute::get_kind_for_name("zeroext").unwrap());
+}
@@ -0,0 +1,120 @@
+extern crate llvm;
+use llvm::*;
+
+#[test]
+fn test_instructions() {
+    let ctx = Context::new();
+    let module = Module::new("block", &ctx);
+    let func = module.add_function("sum", Type::get::<fn(i32, i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    assert_eq!(entry.instructions().count(), 0);
+    assert!(entry.get_terminator().is_none());
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let sum = builder.build_add(&func[0], &func[1]);
+    let ret = builder.build_ret(sum);
+    let instrs: Vec<_> = entry.instructions().collect();
+    assert_eq!(instrs.len(), 2);
+    assert!(instrs[0] == sum);
+    assert!(entry.get_terminator() == Some(ret));
+}
+
+#[test]
+fn test_delete_block() {
+    let ctx = Context::new();
+    let module = Module::new("block", &ctx);
+    let func = module.add_function("nothing", Type::get::<fn() -> ()>(&ctx));
+    func.append("entry");
+    let unused = func.append("unused");
+    unsafe { unused.delete() };
+    assert_eq!(func.get_entry().unwrap().get_name(), Some("entry"));
+    assert!(func.get_entry().unwrap().get_parent().is_some());
+}
+
+#[test]
+fn test_successors() {
+    let ctx = Context::new();
+    let module = Module::new("diamond", &ctx);
+    let func = module.add_function("choose", Type::get::<fn(bool) -> ()>(&ctx));
+    let entry = func.append("entry");
+    let left = func.append("left");
+    let right = func.append("right");
+    let merge = func.append("merge");
+    assert!(entry.successors().is_empty());
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_cond_br(&func[0], left, right);
+    builder.position_at_end(left);
+    builder.build_br(merge);
+    builder.position_at_end(right);
+    builder.build_br(merge);
+    builder.position_at_end(merge);
+    builder.build_ret_void();
+    assert_eq!(entry.successors().len(), 2);
+    assert!(entry.successors().contains(&left));
+    assert!(entry.successors().contains(&right));
+    assert!(left.successors() == vec![merge]);
+    assert!(merge.successors().is_empty());
+    assert!(merge.get_parent().unwrap() == &*func);
+}
+
+#[test]
+fn test_move_blocks() {
+    let ctx = Context::new();
+    let module = Module::new("order", &ctx);
+    let func = module.add_function("order", Type::get::<fn() -> ()>(&ctx));
+    let first = func.append("first");
+    let last = func.append("last");
+    assert!(func.get_entry() == Some(first));
+    last.move_before(first);
+    assert!(func.get_entry() == Some(last));
+    last.move_after(first);
+    assert!(func.get_entry() == Some(first));
+    let inserted = func.insert_block_before(first, "inserted");
+    assert!(func.get_entry() == Some(inserted));
+    let text = format!("{:?}", func);
+    let (inserted, first, last) = (text.find("inserted:"), text.find("first:"), text.find("last:"));
+    assert!(inserted < first && first < last);
+}
+
+#[test]
+fn test_blocks() {
+    let ctx = Context::new();
+    let module = Module::new("blocks", &ctx);
+    let func = module.add_function("blocks", Type::get::<fn() -> ()>(&ctx));
+    assert_eq!(func.blocks().count(), 0);
+    assert_eq!(func.get_block_count(), 0);
+    let names = ["entry", "then", "else"];
+    for name in &names {
+        func.append(name);
+    }
+    assert_eq!(func.get_block_count(), 3);
+    let found: Vec<_> = func.blocks().map(|block| block.get_name().unwrap()).collect();
+    assert_eq!(found, names);
+    let reversed: Vec<_> = func.blocks().rev().map(|block| block.get_name().unwrap()).collect();
+    assert_eq!(reversed, ["else", "then", "entry"]);
+    assert_eq!((&*func).into_iter().count(), 3);
+    let mut blocks = func.blocks();
+    assert_eq!(blocks.get_min().unwrap().get_name(), Some("entry"));
+    assert_eq!(blocks.get_max().unwrap().get_name(), Some("else"));
+    blocks.next();
+    blocks.next_back();
+    assert!(blocks.get_min() == blocks.get_max());
+    blocks.next();
+    assert!(blocks.get_min().is_none() && blocks.get_max().is_none());
+}
+
+#[test]
+fn test_find_instruction() {
+    let ctx = Context::new();
+    let module = Module::new("find", &ctx);
+    let func = module.add_function("add", Type::get::<fn(i32, i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let sum = builder.build_add(&func[0], &func[1]);
+    sum.set_name("sum");
+    builder.build_ret(sum);
+    assert!(entry.find_instruction("sum") == Some(sum));
+    assert!(entry.find_instruction("missing").is_none());
+}
@@ -0,0 +1,17 @@
+extern crate llvm;
+use llvm::*;
+
+#[test]
+fn test_buffer_from_slice() {
+    let data: [u8; 6] = [0xde, 0xc0, 0x17, 0x0b, 0x00, 0xff];
+    let buffer = MemoryBuffer::new_from_slice(&data, "data");
+    assert_eq!(buffer.get_size(), data.len());
+    assert_eq!(buffer.as_slice(), &data);
+    let empty = MemoryBuffer::new_from_slice(&[], "empty");
+    assert_eq!(empty.as_slice().len(), 0);
+}
+
+#[test]
+fn test_buffer_from_missing_file() {
+    assert!(MemoryBuffer::new_from_file("/nonexistent/file.bc").is_err());
+}
@@ -0,0 +1,770 @@
+extern crate llvm;
+use llvm::*;
+
+#[test]
+fn test_build_add() {
+    let ctx = Context::new();
+    let module = Module::new("add", &ctx);
+    let func = module.add_function("add", Type::get::<fn(i32, i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let sum = builder.build_add(&func[0], &func[1]);
+    let ret = builder.build_ret(sum);
+    builder.position_before(ret);
+    builder.build_mul(&func[0], &func[1]);
+    let text = format!("{:?}", func);
+    assert!(text.contains("add i32 %0, %1"));
+    assert!(text.find("mul i32").unwrap() < text.find("ret i32").unwrap());
+}
+
+#[test]
+fn test_build_ret() {
+    let ctx = Context::new();
+    let module = Module::new("ret", &ctx);
+    let func = module.add_function("id", Type::get::<fn(i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_ret(&func[0]);
+    assert!(module.verify().is_ok());
+    let func = module.add_function("nothing", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    builder.position_at_end(entry);
+    builder.build_ret_void();
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+fn test_build_ret_twice() {
+    let ctx = Context::new();
+    let module = Module::new("ret", &ctx);
+    let func = module.add_function("nothing", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_ret_void();
+    builder.build_ret_void();
+    assert!(module.verify().is_err());
+}
+
+#[test]
+fn test_build_cond_br() {
+    let ctx = Context::new();
+    let module = Module::new("diamond", &ctx);
+    let func = module.add_function("choose", Type::get::<fn(bool) -> ()>(&ctx));
+    let entry = func.append("entry");
+    let left = func.append("left");
+    let right = func.append("right");
+    let merge = func.append("merge");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_cond_br(&func[0], left, right);
+    builder.position_at_end(left);
+    builder.build_br(merge);
+    builder.position_at_end(right);
+    builder.build_br(merge);
+    builder.position_at_end(merge);
+    builder.build_ret_void();
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_build_cond_br_not_bool() {
+    let ctx = Context::new();
+    let module = Module::new("diamond", &ctx);
+    let func = module.add_function("choose", Type::get::<fn(i32) -> ()>(&ctx));
+    let entry = func.append("entry");
+    let other = func.append("other");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_cond_br(&func[0], other, other);
+}
+
+#[test]
+fn test_build_memory() {
+    let ctx = Context::new();
+    let module = Module::new("memory", &ctx);
+    let func = module.add_function("answer", Type::get::<fn() -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let ty = Type::get::<i32>(&ctx);
+    let ptr = builder.build_alloca(ty);
+    builder.build_store(42i32.compile(&ctx), ptr);
+    let value = builder.build_load(ptr);
+    assert_eq!(value.get_type(), ty);
+    builder.build_ret(value);
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+fn test_build_icmp() {
+    let ctx = Context::new();
+    let module = Module::new("icmp", &ctx);
+    let func = module.add_function("less", Type::get::<fn(i32, i32) -> ()>(&ctx));
+    let entry = func.append("entry");
+    let then = func.append("then");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let cond = builder.build_icmp(IntPredicate::Slt, &func[0], &func[1]);
+    assert_eq!(cond.get_type(), Type::get::<bool>(&ctx));
+    assert!(format!("{:?}", cond).contains("icmp slt i32 %0, %1"));
+    builder.build_cond_br(cond, then, then);
+    builder.position_at_end(then);
+    builder.build_ret_void();
+    let folded = builder.build_icmp(IntPredicate::Slt, 1i32.compile(&ctx), 2i32.compile(&ctx));
+    assert_eq!(format!("{:?}", folded), "i1 true");
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_build_icmp_floats() {
+    let ctx = Context::new();
+    let builder = Builder::new(&ctx);
+    builder.build_icmp(IntPredicate::Eq, 1.0f64.compile(&ctx), 2.0f64.compile(&ctx));
+}
+
+#[test]
+fn test_build_fcmp() {
+    let ctx = Context::new();
+    let module = Module::new("fcmp", &ctx);
+    let func = module.add_function("same", Type::get::<fn(f64, f64) -> bool>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let ordered = builder.build_fcmp(RealPredicate::Oeq, &func[0], &func[1]);
+    let unordered = builder.build_fcmp(RealPredicate::Ueq, &func[0], &func[1]);
+    assert_eq!(ordered.get_type(), Type::get::<bool>(&ctx));
+    assert!(format!("{:?}", ordered).contains("fcmp oeq double %0, %1"));
+    assert!(format!("{:?}", unordered).contains("fcmp ueq double %0, %1"));
+    builder.build_ret(ordered);
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_build_fcmp_ints() {
+    let ctx = Context::new();
+    let builder = Builder::new(&ctx);
+    builder.build_fcmp(RealPredicate::Oeq, 1i32.compile(&ctx), 2i32.compile(&ctx));
+}
+
+#[test]
+fn test_build_call() {
+    let ctx = Context::new();
+    let module = Module::new("call", &ctx);
+    let callee = module.add_function("callee", Type::get::<fn(i32, i32) -> i32>(&ctx));
+    let func = module.add_function("caller", Type::get::<fn() -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let result = builder.build_call(callee, &[1i32.compile(&ctx), 2i32.compile(&ctx)]);
+    assert_eq!(result.get_type(), Type::get::<i32>(&ctx));
+    builder.build_ret(result);
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_build_call_wrong_args() {
+    let ctx = Context::new();
+    let module = Module::new("call", &ctx);
+    let callee = module.add_function("callee", Type::get::<fn(i32, i32) -> i32>(&ctx));
+    let func = module.add_function("caller", Type::get::<fn() -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_call(callee, &[1i32.compile(&ctx)]);
+}
+
+#[test]
+fn test_build_struct_gep() {
+    let ctx = Context::new();
+    let module = Module::new("gep", &ctx);
+    let func = module.add_function("field", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let pair = StructType::new(&ctx, &[Type::get::<i32>(&ctx), Type::get::<i64>(&ctx)], false);
+    let ptr = builder.build_alloca(pair);
+    let field = builder.build_struct_gep(ptr, 1);
+    assert_eq!(field.get_type(), PointerType::new(Type::get::<i64>(&ctx)));
+    let index = builder.build_gep(ptr, &[0i32.compile(&ctx), 0i32.compile(&ctx)]);
+    assert_eq!(index.get_type(), PointerType::new(Type::get::<i32>(&ctx)));
+    builder.build_ret_void();
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_build_struct_gep_out_of_range() {
+    let ctx = Context::new();
+    let module = Module::new("gep", &ctx);
+    let func = module.add_function("field", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let pair = StructType::new(&ctx, &[Type::get::<i32>(&ctx), Type::get::<i64>(&ctx)], false);
+    let ptr = builder.build_alloca(pair);
+    builder.build_struct_gep(ptr, 2);
+}
+
+#[test]
+fn test_build_casts() {
+    let ctx = Context::new();
+    let module = Module::new("casts", &ctx);
+    let func = module.add_function("casts", Type::get::<fn(i64, i32) -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let (i32_t, i64_t) = (Type::get::<i32>(&ctx), Type::get::<i64>(&ctx));
+    let narrow = builder.build_trunc(&func[0], i32_t);
+    assert_eq!(narrow.get_type(), i32_t);
+    assert!(format!("{:?}", narrow).contains("trunc i64 %0 to i32"));
+    let wide = builder.build_sext(&func[1], i64_t);
+    assert_eq!(wide.get_type(), i64_t);
+    assert!(format!("{:?}", wide).contains("sext i32 %1 to i64"));
+    builder.build_ret_void();
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_build_trunc_wider() {
+    let ctx = Context::new();
+    let module = Module::new("casts", &ctx);
+    let func = module.add_function("casts", Type::get::<fn(i32) -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_trunc(&func[0], Type::get::<i64>(&ctx));
+}
+
+#[test]
+fn test_build_phi() {
+    let ctx = Context::new();
+    let module = Module::new("phi", &ctx);
+    let func = module.add_function("count", Type::get::<fn(i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let body = func.append("loop");
+    let exit = func.append("exit");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_br(body);
+    builder.position_at_end(body);
+    let counter = builder.build_phi(Type::get::<i32>(&ctx));
+    let next = builder.build_add(counter, 1i32.compile(&ctx));
+    counter.add_incoming(&[(0i32.compile(&ctx), entry)]);
+    counter.add_incoming(&[(next, body)]);
+    assert_eq!(counter.count_incoming(), 2);
+    let done = builder.build_icmp(IntPredicate::Sge, next, &func[0]);
+    builder.build_cond_br(done, exit, body);
+    builder.position_at_end(exit);
+    builder.build_ret(counter);
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+fn test_build_select() {
+    let ctx = Context::new();
+    let module = Module::new("select", &ctx);
+    let func = module.add_function("max", Type::get::<fn(i32, i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let cond = builder.build_icmp(IntPredicate::Sgt, &func[0], &func[1]);
+    let value = builder.build_select(cond, 1i32.compile(&ctx), 2i32.compile(&ctx));
+    assert_eq!(value.get_type(), Type::get::<i32>(&ctx));
+    builder.build_ret(value);
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+fn test_build_bitwise() {
+    let ctx = Context::new();
+    let module = Module::new("bitwise", &ctx);
+    let func = module.add_function("flag", Type::get::<fn(i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let shifted = builder.build_shl(&func[0], 2i32.compile(&ctx));
+    let value = builder.build_or(shifted, 1i32.compile(&ctx));
+    builder.build_ret(value);
+    let text = format!("{:?}", func);
+    let shl = text.find("shl i32 %0, 2").unwrap();
+    let or = text.find("or i32 %2, 1").unwrap();
+    assert!(shl < or);
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+#[should_panic]
+fn test_build_bitwise_float() {
+    let ctx = Context::new();
+    let module = Module::new("bitwise", &ctx);
+    let func = module.add_function("flag", Type::get::<fn(f64, f64) -> f64>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_xor(&func[0], &func[1]);
+}
+
+#[test]
+fn test_build_global_string_ptr() {
+    let ctx = Context::new();
+    let module = Module::new("strings", &ctx);
+    let func = module.add_function("greeting", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let ptr = builder.build_global_string_ptr("hello", "greeting_str");
+    assert_eq!(ptr.get_type(), PointerType::new(Type::get::<i8>(&ctx)));
+    assert!(module.get_global("greeting_str").is_some());
+}
+
+#[test]
+#[should_panic]
+fn test_build_global_string_ptr_null() {
+    let ctx = Context::new();
+    let module = Module::new("strings", &ctx);
+    let func = module.add_function("greeting", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_global_string_ptr("hel\0lo", "greeting_str");
+}
+
+#[test]
+fn test_build_switch() {
+    let ctx = Context::new();
+    let module = Module::new("switch", &ctx);
+    let func = module.add_function("classify", Type::get::<fn(i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let default = func.append("default");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let switch = builder.build_switch(&func[0], default, &[]);
+    assert!(switch.get_condition() == &*func[0]);
+    for i in 0..3 {
+        let case = func.append("case");
+        switch.add_case(i.compile(&ctx), case);
+        builder.position_at_end(case);
+        builder.build_ret((i * 10).compile(&ctx));
+    }
+    builder.position_at_end(default);
+    builder.build_ret((-1i32).compile(&ctx));
+    assert_eq!(entry.successors().len(), 4);
+    module.verify().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "does not match condition type")]
+fn test_build_switch_mismatched_case() {
+    let ctx = Context::new();
+    let module = Module::new("switch", &ctx);
+    let func = module.add_function("classify", Type::get::<fn(i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_switch(&func[0], entry, &[(0u8.compile(&ctx), entry)]);
+}
+
+#[test]
+fn test_build_unreachable() {
+    let ctx = Context::new();
+    let module = Module::new("unreachable", &ctx);
+    let abort = module.add_function("abort", Type::get::<fn() -> ()>(&ctx));
+    abort.add_attribute(Attribute::NoReturn);
+    let func = module.add_function("fail", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_call(abort, &[]);
+    let unreachable = builder.build_unreachable();
+    assert!(entry.get_terminator() == Some(unreachable));
+    module.verify().unwrap();
+}
+
+#[test]
+fn test_build_invoke() {
+    let ctx = Context::new();
+    let module = Module::new("invoke", &ctx);
+    let callee = module.add_function("may_throw", Type::get::<fn(i32) -> i32>(&ctx));
+    let func = module.add_function("caller", Type::get::<fn(i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let then = func.append("then");
+    let catch = func.append("catch");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let result = builder.build_invoke(callee, &[&func[0]], then, catch);
+    assert_eq!(entry.successors(), [then, catch]);
+    builder.position_at_end(then);
+    builder.build_ret(result);
+    assert!(format!("{:?}", func).contains("invoke i32 @may_throw(i32 %0)"));
+}
+
+#[test]
+#[should_panic(expected = "expected 1 arguments")]
+fn test_build_invoke_wrong_args() {
+    let ctx = Context::new();
+    let module = Module::new("invoke", &ctx);
+    let callee = module.add_function("may_throw", Type::get::<fn(i32) -> i32>(&ctx));
+    let func = module.add_function("caller", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_invoke(callee, &[], entry, entry);
+}
+
+#[test]
+fn test_build_float_ops() {
+    let ctx = Context::new();
+    let module = Module::new("float", &ctx);
+    let func = module.add_function("fma", Type::get::<fn(f64, f64, f64) -> f64>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let product = builder.build_fmul(&func[0], &func[1]);
+    let sum = builder.build_fadd(product, &func[2]);
+    builder.build_ret(sum);
+    module.verify().unwrap();
+    let text = format!("{:?}", func);
+    assert!(text.contains("fmul double %0, %1"));
+    assert!(text.contains("fadd double"));
+}
+
+#[test]
+#[should_panic(expected = "expected floating-point operands")]
+fn test_build_float_op_on_ints() {
+    let ctx = Context::new();
+    let module = Module::new("float", &ctx);
+    let func = module.add_function("add", Type::get::<fn(i32, i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_fadd(&func[0], &func[1]);
+}
+
+#[test]
+fn test_build_no_wrap() {
+    let ctx = Context::new();
+    let module = Module::new("no_wrap", &ctx);
+    let func = module.add_function("scale", Type::get::<fn(i32, i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let sum = builder.build_nsw_add(&func[0], &func[1]);
+    let product = builder.build_nuw_mul(sum, &func[1]);
+    builder.build_ret(product);
+    module.verify().unwrap();
+    let text = format!("{:?}", func);
+    assert!(text.contains("add nsw i32 %0, %1"));
+    assert!(text.contains("mul nuw i32"));
+}
+
+#[test]
+fn test_build_atomics() {
+    let ctx = Context::new();
+    let module = Module::new("atomics", &ctx);
+    let func = module.add_function("increment", Type::get::<fn(*mut i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let counter = &func[0];
+    let old = builder.build_atomic_rmw(AtomicRMWBinOp::Add, counter, 1i32.compile(&ctx), AtomicOrdering::SeqCst, false);
+    let store = builder.build_store(old, counter);
+    assert_eq!(store.get_ordering(), AtomicOrdering::NotAtomic);
+    store.set_alignment(4);
+    store.set_ordering(AtomicOrdering::Release);
+    assert_eq!(store.get_ordering(), AtomicOrdering::Release);
+    builder.build_ret(old);
+    module.verify().unwrap();
+    let text = format!("{:?}", func);
+    assert!(text.contains("atomicrmw add i32* %0, i32 1 seq_cst"));
+    assert!(text.contains("store atomic i32"));
+}
+
+#[test]
+fn test_build_fence() {
+    let ctx = Context::new();
+    let module = Module::new("fence", &ctx);
+    let func = module.add_function("barrier", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_fence(AtomicOrdering::SeqCst, false);
+    builder.build_fence(AtomicOrdering::Acquire, true);
+    builder.build_ret_void();
+    module.verify().unwrap();
+    let text = format!("{:?}", func);
+    assert!(text.contains("fence seq_cst"));
+    assert!(text.contains("fence singlethread acquire"));
+}
+
+#[test]
+fn test_build_extract_value() {
+    let ctx = Context::new();
+    let module = Module::new("aggregates", &ctx);
+    let pair = StructType::new(&ctx, &[Type::get::<i32>(&ctx), Type::get::<f64>(&ctx)], false);
+    let func = module.add_function("second", FunctionType::new(Type::get::<f64>(&ctx), &[&**pair], false));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let second = builder.build_extract_value(&func[0], 1);
+    assert!(second.get_type() == Type::get::<f64>(&ctx));
+    let updated = builder.build_insert_value(&func[0], 5i32.compile(&ctx), 0);
+    assert!(updated.get_type() == &**pair);
+    builder.build_ret(second);
+    module.verify().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "no such field 2")]
+fn test_build_extract_value_out_of_range() {
+    let ctx = Context::new();
+    let module = Module::new("aggregates", &ctx);
+    let pair = StructType::new(&ctx, &[Type::get::<i32>(&ctx), Type::get::<f64>(&ctx)], false);
+    let func = module.add_function("third", FunctionType::new(Type::get::<f64>(&ctx), &[&**pair], false));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_extract_value(&func[0], 2);
+}
+
+#[test]
+fn test_build_shuffle_vector() {
+    let ctx = Context::new();
+    let module = Module::new("vectors", &ctx);
+    let vec_t = VectorType::new(Type::get::<f32>(&ctx), 4);
+    let func = module.add_function("reverse", FunctionType::new(vec_t, &[&**vec_t, &**vec_t], false));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let indices: Vec<_> = [3i32, 2, 1, 0].iter().map(|&i| i.compile(&ctx)).collect();
+    let mask = Value::new_vector(&indices);
+    let reversed = builder.build_shuffle_vector(&func[0], &func[1], mask);
+    assert!(reversed.get_type() == &**vec_t);
+    let first = builder.build_extract_element(reversed, 0i32.compile(&ctx));
+    assert!(first.get_type() == Type::get::<f32>(&ctx));
+    let result = builder.build_insert_element(reversed, first, 3i32.compile(&ctx));
+    builder.build_ret(result);
+    module.verify().unwrap();
+    assert!(format!("{:?}", func).contains("shufflevector <4 x float> %0, <4 x float> %1, <4 x i32> <i32 3, i32 2, i32 1, i32 0>"));
+}
+
+#[test]
+#[should_panic(expected = "expected a constant vector of i32 as the mask")]
+fn test_build_shuffle_vector_variable_mask() {
+    let ctx = Context::new();
+    let module = Module::new("vectors", &ctx);
+    let vec_t = VectorType::new(Type::get::<i32>(&ctx), 4);
+    let func = module.add_function("shuffle", FunctionType::new(vec_t, &[&**vec_t, &**vec_t], false));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_shuffle_vector(&func[0], &func[0], &func[1]);
+}
+
+#[test]
+fn test_build_negation() {
+    let ctx = Context::new();
+    let module = Module::new("negation", &ctx);
+    let func = module.add_function("negate", Type::get::<fn(i32, f64) -> f64>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_neg(&func[0]);
+    builder.build_not(&func[0]);
+    let negated = builder.build_fneg(&func[1]);
+    builder.build_ret(negated);
+    module.verify().unwrap();
+    let text = format!("{:?}", func);
+    assert!(text.contains("sub i32 0, %0"));
+    assert!(text.contains("xor i32 %0, -1"));
+    assert!(text.contains("fsub double -0.000000e+00, %1"));
+}
+
+#[test]
+fn test_builder_drop() {
+    let ctx = Context::new();
+    let module = Module::new("builders", &ctx);
+    let func = module.add_function("count", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    for _ in 0..1000 {
+        let builder = Builder::new(&ctx);
+        builder.position_at_end(entry);
+        builder.build_alloca(Type::get::<i32>(&ctx));
+    }
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_ret_void();
+    assert_eq!(entry.instructions().count(), 1001);
+    module.verify().unwrap();
+}
+
+#[test]
+fn test_get_insert_block() {
+    let ctx = Context::new();
+    let module = Module::new("insert", &ctx);
+    let func = module.add_function("locals", Type::get::<fn() -> i32>(&ctx));
+    let entry = func.append("entry");
+    let body = func.append("body");
+    let builder = Builder::new(&ctx);
+    assert!(builder.get_insert_block().is_none());
+    builder.position_at_end(entry);
+    let jump = builder.build_br(body);
+    builder.position_at_end(body);
+    let saved = builder.get_insert_block().unwrap();
+    assert!(saved == body);
+    builder.position_before(jump);
+    let slot = builder.build_alloca(Type::get::<i32>(&ctx));
+    assert!(builder.get_insert_block() == Some(entry));
+    builder.position_at_end(saved);
+    builder.build_store(7i32.compile(&ctx), slot);
+    builder.build_ret(builder.build_load(slot));
+    module.verify().unwrap();
+    assert!(entry.get_first() == Some(slot));
+    assert_eq!(body.instructions().count(), 3);
+}
+
+#[test]
+fn test_build_memcpy() {
+    let ctx = Context::new();
+    let module = Module::new("memory", &ctx);
+    let func = module.add_function("copy", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let array_t = Type::get_array(Type::get_i32(&ctx), 4);
+    let (src, dst) = (builder.build_alloca(array_t), builder.build_alloca(array_t));
+    let size = 16u64.compile(&ctx);
+    builder.build_memset(src, 0u8.compile(&ctx), size, 4);
+    builder.build_memcpy(dst, src, size, 4);
+    builder.build_memmove(src, dst, size, 4);
+    builder.build_ret_void();
+    module.verify().unwrap();
+    assert!(module.get_function("llvm.memcpy.p0i8.p0i8.i64").unwrap().get_intrinsic_id().is_some());
+    let text = format!("{:?}", func);
+    assert!(text.contains("call void @llvm.memcpy.p0i8.p0i8.i64(i8* %"));
+    assert!(text.contains("i64 16, i32 4, i1 false)"));
+    assert!(text.contains("call void @llvm.memset.p0i8.i64"));
+    assert!(text.contains("call void @llvm.memmove.p0i8.p0i8.i64"));
+}
+
+#[test]
+#[should_panic]
+fn test_build_memset_mismatched_declaration() {
+    let ctx = Context::new();
+    let module = Module::new("memory", &ctx);
+    module.add_function("llvm.memset.p0i8.i64", Type::get::<fn() -> ()>(&ctx));
+    let func = module.add_function("clear", Type::get::<fn() -> ()>(&ctx));
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(func.append("entry"));
+    let slot = builder.build_alloca(Type::get_i64(&ctx));
+    builder.build_memset(slot, 0u8.compile(&ctx), 8u64.compile(&ctx), 8);
+}
+
+#[test]
+fn test_build_va_arg() {
+    let ctx = Context::new();
+    let module = Module::new("varargs", &ctx);
+    let i32_t = Type::get_i32(&ctx);
+    let byte_ptr = Type::get_i8(&ctx).pointer_to();
+    let va_start = module.add_function("llvm.va_start", FunctionType::new(Type::get_void(&ctx), &[byte_ptr], false));
+    let va_end = module.add_function("llvm.va_end", FunctionType::new(Type::get_void(&ctx), &[byte_ptr], false));
+    let func = module.add_function("first", FunctionType::new(i32_t, &[i32_t], true));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let list = builder.build_alloca(byte_ptr);
+    let list_ptr = builder.build_bit_cast(list, byte_ptr);
+    builder.build_call(va_start, &[list_ptr]);
+    let value = builder.build_va_arg(list, i32_t);
+    assert!(value.get_type() == i32_t);
+    builder.build_call(va_end, &[list_ptr]);
+    builder.build_ret(value);
+    module.verify().unwrap();
+    assert!(format!("{:?}", func).contains("va_arg i8** %"));
+}
+
+#[test]
+fn test_build_landing_pad() {
+    let ctx = Context::new();
+    let module = Module::new("exceptions", &ctx);
+    let i32_t = Type::get_i32(&ctx);
+    let byte_ptr = Type::get_i8(&ctx).pointer_to();
+    let personality = module.add_function("__gxx_personality_v0", FunctionType::new(i32_t, &[], true));
+    let callee = module.add_function("may_throw", Type::get::<fn() -> ()>(&ctx));
+    let func = module.add_function("caller", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    let then = func.append("then");
+    let catch = func.append("catch");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    builder.build_invoke(callee, &[], then, catch);
+    builder.position_at_end(then);
+    builder.build_ret_void();
+    builder.position_at_end(catch);
+    let exn_t = StructType::new(&ctx, &[byte_ptr, i32_t], false);
+    let pad = builder.build_landing_pad(exn_t, personality, &[Value::new_null(byte_ptr)]);
+    pad.set_cleanup(true);
+    builder.build_resume(pad);
+    module.verify().unwrap();
+    let text = format!("{:?}", func);
+    assert!(text.contains("personality i32 (...)* @__gxx_personality_v0"));
+    assert!(text.contains("landingpad { i8*, i32 }"));
+    assert!(text.contains("cleanup"));
+    assert!(text.contains("catch i8* null"));
+    assert!(text.contains("resume { i8*, i32 }"));
+}
+
+const DEBUG_IR: &'static str = r#"
+define void @f() !dbg !4 {
+  ret void, !dbg !7
+}
+
+!llvm.dbg.cu = !{!0}
+!llvm.module.flags = !{!3}
+
+!0 = distinct !DICompileUnit(language: DW_LANG_C99, file: !1, producer: "test", isOptimized: false, runtimeVersion: 0, emissionKind: FullDebug, enums: !2)
+!1 = !DIFile(filename: "test.c", directory: "/tmp")
+!2 = !{}
+!3 = !{i32 2, !"Debug Info Version", i32 3}
+!4 = distinct !DISubprogram(name: "f", scope: !1, file: !1, line: 1, type: !5, isLocal: false, isDefinition: true, scopeLine: 1, isOptimized: false, unit: !0, variables: !2)
+!5 = !DISubroutineType(types: !6)
+!6 = !{null}
+!7 = !DILocation(line: 3, column: 5, scope: !4)
+"#;
+
+#[test]
+fn test_debug_location() {
+    let ctx = Context::new();
+    let module = Module::parse_ir(&ctx, DEBUG_IR).unwrap();
+    let dbg = ctx.get_metadata_kind_id("dbg");
+    let entry = module.get_function("f").unwrap().get_entry().unwrap();
+    let ret = entry.get_terminator().unwrap();
+    let loc = ret.get_metadata(dbg).unwrap();
+    let builder = Builder::new(&ctx);
+    builder.position_before(ret);
+    assert!(builder.get_current_debug_location().is_none());
+    builder.set_current_debug_location(loc);
+    assert!(builder.get_current_debug_location() == Some(loc));
+    let slot = builder.build_alloca(Type::get_i32(&ctx));
+    assert!(slot.get_metadata(dbg) == Some(loc));
+    module.verify().unwrap();
+    assert!(format!("{:?}", slot).contains("!dbg !7"));
+}
+
+#[test]
+#[should_panic]
+fn test_debug_location_not_metadata() {
+    let ctx = Context::new();
+    let module = Module::parse_ir(&ctx, DEBUG_IR).unwrap();
+    let ret = module.get_function("f").unwrap().get_entry().unwrap().get_terminator().unwrap();
+    let builder = Builder::new(&ctx);
+    builder.position_before(ret);
+    builder.set_current_debug_location(5i32.compile(&ctx));
+}
@@ -0,0 +1,167 @@
+extern crate llvm;
+use llvm::*;
+
+#[test]
+fn test_new_int() {
+    let ctx = Context::new();
+    let ty = Type::get::<i64>(&ctx);
+    let value = Value::new_int(ty, -1i64 as u64, true);
+    let int_ty = IntegerType::from_super(value.get_type()).unwrap();
+    assert_eq!(int_ty.get_width(), 64);
+    assert_eq!(format!("{:?}", value), "i64 -1");
+}
+
+#[test]
+fn test_new_bool() {
+    let ctx = Context::new();
+    let value = Value::new_bool(&ctx, true);
+    assert_eq!(format!("{:?}", value), "i1 true");
+}
+
+#[test]
+fn test_new_real() {
+    let ctx = Context::new();
+    let ty = Type::get::<f64>(&ctx);
+    let value = Value::new_real(ty, 3.14);
+    assert_eq!(format!("{:?}", value), "double 3.140000e+00");
+    let value = Value::new_real_from_str(ty, "3.14");
+    assert_eq!(format!("{:?}", value), "double 3.140000e+00");
+    let quad = Type::get_fp128(&ctx);
+    assert_eq!(Value::new_real(quad, 1.5).get_type(), quad);
+    assert_eq!(Value::new_real_from_str(quad, "1.5").get_type(), quad);
+}
+
+#[test]
+#[should_panic]
+fn test_new_real_not_float() {
+    let ctx = Context::new();
+    Value::new_real(Type::get::<i32>(&ctx), 1.0);
+}
+
+#[test]
+fn test_new_string() {
+    let ctx = Context::new();
+    let value = Value::new_string(&ctx, "hi", true);
+    assert_eq!(format!("{:?}", value.get_type()), "[3 x i8]");
+    let value = Value::new_string(&ctx, "a\0b", false);
+    assert_eq!(format!("{:?}", value.get_type()), "[3 x i8]");
+}
+
+#[test]
+fn test_new_vector() {
+    let ctx = Context::new();
+    let vals: Vec<_> = (0..4).map(|i: i32| i.compile(&ctx)).collect();
+    let value = Value::new_vector(&vals);
+    assert_eq!(format!("{:?}", value.get_type()), "<4 x i32>");
+}
+
+#[test]
+#[should_panic]
+fn test_new_vector_mismatch() {
+    let ctx = Context::new();
+    Value::new_vector(&[1i32.compile(&ctx), 1i64.compile(&ctx)]);
+}
+
+#[test]
+fn test_new_array_empty() {
+    let ctx = Context::new();
+    let value = Value::new_array(Type::get::<i32>(&ctx), &[]);
+    assert_eq!(format!("{:?}", value.get_type()), "[0 x i32]");
+}
+
+#[test]
+fn test_new_null() {
+    let ctx = Context::new();
+    let int_ty = Type::get::<i32>(&ctx);
+    assert_eq!(format!("{:?}", Value::new_null(PointerType::new(int_ty))), "i32* null");
+    assert_eq!(format!("{:?}", Value::new_null(int_ty)), "i32 0");
+    assert_eq!(format!("{:?}", Value::new_all_ones(int_ty)), "i32 -1");
+    assert_eq!(format!("{:?}", Value::new_undef(int_ty)), "i32 undef");
+}
+
+#[test]
+fn test_is_constant() {
+    let ctx = Context::new();
+    let module = Module::new("constants", &ctx);
+    let func = module.add_function("id", Type::get::<fn(i32) -> i32>(&ctx));
+    let ty = Type::get::<i32>(&ctx);
+    assert!(Value::new_int(ty, 42, false).is_constant());
+    assert!(!func[0].is_constant());
+    assert!(Value::new_undef(ty).is_undef());
+    assert!(Value::new_null(ty).is_null());
+    assert!(!Value::new_int(ty, 1, false).is_null());
+}
+
+#[test]
+fn test_const_expressions() {
+    let ctx = Context::new();
+    let (two, three) = (2i32.compile(&ctx), 3i32.compile(&ctx));
+    assert_eq!(format!("{:?}", Value::const_add(two, three)), "i32 5");
+    assert_eq!(format!("{:?}", Value::const_mul(two, three)), "i32 6");
+    assert_eq!(format!("{:?}", Value::const_neg(two)), "i32 -2");
+    let cast = Value::const_bitcast(Value::new_int(Type::get::<i32>(&ctx), 0, false), Type::get::<f32>(&ctx));
+    assert!(cast.get_type() == Type::get::<f32>(&ctx));
+    assert!(cast.is_null());
+}
+
+#[test]
+#[should_panic(expected = "is not a constant")]
+fn test_const_expression_on_variable() {
+    let ctx = Context::new();
+    let module = Module::new("consts", &ctx);
+    let func = module.add_function("add", Type::get::<fn(i32) -> i32>(&ctx));
+    Value::const_add(&func[0], 1i32.compile(&ctx));
+}
+
+#[test]
+fn test_const_pointer_casts() {
+    let ctx = Context::new();
+    let ptr_t = Type::get::<*const i32>(&ctx);
+    let null = Value::new_null(ptr_t);
+    let address = Value::const_ptr_to_int(null, Type::get::<u64>(&ctx));
+    assert_eq!(format!("{:?}", address), "i64 0");
+    let back = Value::const_int_to_ptr(address, ptr_t);
+    assert!(back.get_type() == ptr_t);
+    assert!(back.is_null());
+}
+
+#[test]
+fn test_const_zero() {
+    let ctx = Context::new();
+    let module = Module::new("zeros", &ctx);
+    let array_t = Type::get_array(Type::get_i32(&ctx), 1024);
+    let zero = Value::const_zero(array_t);
+    assert!(zero.is_null());
+    let global = module.add_global_variable("table", zero);
+    assert!(format!("{:?}", global).contains("@table = global [1024 x i32] zeroinitializer"));
+    assert_eq!(format!("{:?}", Value::const_zero(Type::get_i32(&ctx))), "i32 0");
+}
+
+#[test]
+fn test_int_value_extraction() {
+    let ctx = Context::new();
+    let answer = Value::new_int(Type::get_i32(&ctx), 42, false);
+    assert_eq!(answer.get_zext_value(), Some(42));
+    assert_eq!(answer.get_sext_value(), Some(42));
+    let minus_one = (-1i8).compile(&ctx);
+    assert_eq!(minus_one.get_zext_value(), Some(255));
+    assert_eq!(minus_one.get_sext_value(), Some(-1));
+    assert_eq!(1.5f64.compile(&ctx).get_zext_value(), None);
+    let module = Module::new("ints", &ctx);
+    let func = module.add_function("id", Type::get::<fn(i32) -> i32>(&ctx));
+    assert_eq!(func[0].get_zext_value(), None);
+    assert_eq!(func[0].get_sext_value(), None);
+    let wide = Value::new_int(Type::get_int(&ctx, 128), 42, false);
+    assert_eq!(wide.get_zext_value(), None);
+    assert_eq!(wide.get_sext_value(), None);
+}
+
+#[test]
+fn test_double_value_extraction() {
+    let ctx = Context::new();
+    let value = Value::new_real(Type::get_double(&ctx), 2.5);
+    assert_eq!(value.get_double_value(), Some((2.5, false)));
+    let single = Value::new_real(Type::get_float(&ctx), 0.5);
+    assert_eq!(single.get_double_value(), Some((0.5, false)));
+    assert_eq!(1i32.compile(&ctx).get_double_value(), None);
+}
@@ -0,0 +1,49 @@
+extern crate llvm;
+use llvm::*;
+
+#[test]
+fn test_context_drop() {
+    for _ in 0..4 {
+        let ctx = Context::new();
+        let ty = Type::get::<i32>(&ctx);
+        assert!(ty.get_context() == &*ctx);
+        let module = Module::new("owned", &ctx);
+        assert!(module.get_context() == &*ctx);
+    }
+}
+
+#[test]
+fn test_global_context() {
+    let global = unsafe { Context::get_global() };
+    assert!(global == unsafe { Context::get_global() });
+    let ctx = Context::new();
+    assert!(global != &*ctx);
+    let ty = Type::get::<f64>(global);
+    assert!(ty.get_context() == global);
+}
+
+#[test]
+fn test_build_on_thread() {
+    use std::thread;
+    let ctx = ThreadSafeContext::new();
+    let module = thread::spawn(move || {
+        let module = ThreadSafeModule::new(ctx, "worker");
+        {
+            let ctx = module.get_context();
+            let func = module.get_module().add_function("answer", Type::get::<fn() -> i32>(ctx));
+            let builder = Builder::new(ctx);
+            builder.position_at_end(func.append("entry"));
+            builder.build_ret(42i32.compile(ctx));
+        }
+        module
+    }).join().unwrap();
+    module.get_module().verify().unwrap();
+    assert!(module.get_module().get_function("answer").is_some());
+}
+
+#[test]
+fn test_try_new_thread_safe_module() {
+    assert!(ThreadSafeModule::try_new(ThreadSafeContext::new(), "bad\0name").is_err());
+    let module = ThreadSafeModule::try_new(ThreadSafeContext::new(), "good").unwrap();
+    assert!(module.get_module().get_function("answer").is_none());
+}
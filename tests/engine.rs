@@ -0,0 +1,90 @@
+extern crate llvm;
+use llvm::*;
+use std::mem;
+
+fn build_answer(ctx: &Context) -> CSemiBox<Module> {
+    let module = Module::new("answer", ctx);
+    let func = module.add_function("answer", Type::get::<fn() -> i32>(ctx));
+    let builder = Builder::new(ctx);
+    builder.position_at_end(func.append("entry"));
+    builder.build_ret(42i32.compile(ctx));
+    module
+}
+
+#[test]
+fn test_get_function_address() {
+    let ctx = Context::new();
+    let module = build_answer(&ctx);
+    let ee = JitEngine::new(&module, JitOptions {opt_level: 0}).unwrap();
+    assert_eq!(ee.get_function_address("missing"), None);
+    let address = ee.get_function_address("answer").unwrap();
+    let answer: extern fn() -> i32 = unsafe { mem::transmute(address as usize) };
+    assert_eq!(answer(), 42);
+}
+
+#[test]
+fn test_jit_opt_levels() {
+    let ctx = Context::new();
+    for &opt_level in &[0, 2] {
+        let module = build_answer(&ctx);
+        let ee = JitEngine::new(&module, JitOptions {opt_level: opt_level}).unwrap();
+        let address = ee.get_function_address("answer").unwrap();
+        let answer: extern fn() -> i32 = unsafe { mem::transmute(address as usize) };
+        assert_eq!(answer(), 42);
+    }
+}
+
+extern fn host_add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[test]
+fn test_add_global_mapping() {
+    let ctx = Context::new();
+    let module = Module::new("mapping", &ctx);
+    let sig = Type::get::<fn(i32, i32) -> i32>(&ctx);
+    let host = module.add_function("host_add", sig);
+    let func = module.add_function("call_host", sig);
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(func.append("entry"));
+    let sum = builder.build_call(host, &[&func[0], &func[1]]);
+    builder.build_ret(sum);
+    module.verify().unwrap();
+    let ee = JitEngine::new(&module, JitOptions {opt_level: 0}).unwrap();
+    ee.add_global_mapping(host, host_add as *const ());
+    let address = ee.get_function_address("call_host").unwrap();
+    let call_host: extern fn(i32, i32) -> i32 = unsafe { mem::transmute(address as usize) };
+    assert_eq!(call_host(2, 3), 5);
+}
+
+#[test]
+fn test_run_function() {
+    let ctx = Context::new();
+    let module = Module::new("double", &ctx);
+    let func = module.add_function("double", Type::get::<fn(i32) -> i32>(&ctx));
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(func.append("entry"));
+    let doubled = builder.build_mul(&func[0], 2i32.compile(&ctx));
+    builder.build_ret(doubled);
+    module.verify().unwrap();
+    let ee = Interpreter::new(&module, ()).unwrap();
+    let arg = GenericValue::from_int(Type::get::<i32>(&ctx), 21, true);
+    let result = ee.run_function(func, &[&arg]);
+    assert_eq!(result.to_int(true), 42);
+    let arg = (-4i32).to_generic(&ctx);
+    let result = ee.run_function(func, &[&arg]);
+    assert_eq!(i32::from_generic(&result, &ctx), -8);
+}
+
+#[test]
+fn test_alias_address() {
+    let ctx = Context::new();
+    let module = build_answer(&ctx);
+    let func = module.get_function("answer").unwrap();
+    let alias = module.add_global_alias("alias", func);
+    assert_eq!(alias.get_name(), Some("alias"));
+    module.verify().unwrap();
+    let ee = JitEngine::new(&module, JitOptions {opt_level: 0}).unwrap();
+    let address = ee.get_function_address("answer").unwrap();
+    assert_eq!(ee.get_function_address("alias"), Some(address));
+}
@@ -0,0 +1,264 @@
+extern crate llvm;
+use llvm::*;
+
+#[test]
+fn test_add_function() {
+    let ctx = Context::new();
+    let module = Module::new("functions", &ctx);
+    assert!(module.get_function("main").is_none());
+    let func = module.add_function("main", Type::get::<fn() -> i32>(&ctx));
+    let found = module.get_function("main").unwrap();
+    assert!(found == &*func);
+    assert_eq!(found.get_name(), Some("main"));
+}
+
+#[test]
+fn test_verify_missing_terminator() {
+    let ctx = Context::new();
+    let module = Module::new("broken", &ctx);
+    let func = module.add_function("broken", Type::get::<fn() -> ()>(&ctx));
+    func.append("entry");
+    let error = module.verify().unwrap_err();
+    assert!(error.contains("does not have terminator"));
+    assert!(error.contains("entry"));
+}
+
+#[test]
+fn test_bitcode_round_trip() {
+    let ctx = Context::new();
+    let module = Module::new("bitcode", &ctx);
+    module.add_function("add", Type::get::<fn(i32, i32) -> i32>(&ctx));
+    let path = std::env::temp_dir().join(format!("llvm_rs_test_bitcode_round_trip_{}.bc", std::process::id()));
+    module.write_bitcode(&path).unwrap();
+    let parsed = Module::parse_bitcode(&ctx, &path);
+    std::fs::remove_file(&path).unwrap();
+    let parsed = parsed.unwrap();
+    let func = parsed.get_function("add").unwrap();
+    assert_eq!(func.get_signature(), module.get_function("add").unwrap().get_signature());
+}
+
+#[test]
+fn test_parse_bitcode_missing() {
+    let ctx = Context::new();
+    assert!(Module::parse_bitcode(&ctx, "/nonexistent/llvm_rs.bc").is_err());
+}
+
+#[test]
+fn test_parse_ir() {
+    let ctx = Context::new();
+    let module = Module::parse_ir(&ctx, "define i32 @one() {\n  ret i32 1\n}\n").unwrap();
+    let func = module.get_function("one").unwrap();
+    assert_eq!(func.get_signature().get_return(), Type::get::<i32>(&ctx));
+}
+
+#[test]
+fn test_parse_ir_error() {
+    let ctx = Context::new();
+    let error = Module::parse_ir(&ctx, "define i32 @one() {\n  ret i64 1\n}\n").unwrap_err();
+    assert!(error.contains("2:"));
+}
+
+#[test]
+fn test_print_module() {
+    let ctx = Context::new();
+    let module = Module::new("printing", &ctx);
+    module.add_function("printed_function", Type::get::<fn() -> ()>(&ctx));
+    let text = module.to_string();
+    assert!(text.contains("declare void @printed_function()"));
+    assert_eq!(text, format!("{:?}", module));
+    let path = std::env::temp_dir().join(format!("llvm_rs_test_print_module_{}.ll", std::process::id()));
+    module.print_to_file(&path).unwrap();
+    let mut contents = String::new();
+    std::io::Read::read_to_string(&mut std::fs::File::open(&path).unwrap(), &mut contents).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(contents.contains("printed_function"));
+}
+
+#[test]
+fn test_target_and_data_layout() {
+    let ctx = Context::new();
+    let module = Module::new("target", &ctx);
+    assert_eq!(module.get_target(), "");
+    module.set_target("x86_64-unknown-linux-gnu");
+    assert_eq!(module.get_target(), "x86_64-unknown-linux-gnu");
+    assert!(module.try_set_target("x86_64\0linux").is_err());
+    assert_eq!(module.get_target(), "x86_64-unknown-linux-gnu");
+    let layout = "e-m:e-i64:64-f80:128-n8:16:32:64-S128";
+    module.set_data_layout(layout);
+    assert_eq!(module.get_data_layout(), layout);
+}
+
+#[test]
+fn test_functions() {
+    let ctx = Context::new();
+    let module = Module::new("functions", &ctx);
+    assert_eq!(module.functions().count(), 0);
+    let sig = Type::get::<fn() -> ()>(&ctx);
+    let defined = module.add_function("a", sig);
+    defined.append("entry");
+    module.add_function("b", sig);
+    module.add_function("c", sig);
+    let names: Vec<_> = module.functions().map(|func| func.get_name().unwrap()).collect();
+    assert_eq!(names, ["a", "b", "c"]);
+}
+
+#[test]
+fn test_add_global() {
+    let ctx = Context::new();
+    let module = Module::new("globals", &ctx);
+    let global = module.add_global("answer", Type::get::<i32>(&ctx));
+    global.set_initializer(42i32.compile(&ctx));
+    global.set_constant(true);
+    assert!(global.get_constant());
+    assert!(global.get_initializer().unwrap() == 42i32.compile(&ctx));
+    assert!(!global.is_thread_local());
+    global.set_thread_local(true);
+    assert!(global.is_thread_local());
+    assert!(format!("{:?}", global).contains("@answer = thread_local constant i32 42"));
+}
+
+#[test]
+#[should_panic]
+fn test_global_initializer_mismatch() {
+    let ctx = Context::new();
+    let module = Module::new("globals", &ctx);
+    let global = module.add_global("answer", Type::get::<i32>(&ctx));
+    global.set_initializer(42i64.compile(&ctx));
+}
+
+#[test]
+fn test_link_destroy() {
+    let ctx = Context::new();
+    let sig = Type::get::<fn() -> ()>(&ctx);
+    let module = Module::new("main", &ctx);
+    module.add_function("foo", sig);
+    assert!(module.get_function("foo").unwrap().is_declaration());
+    let other = Module::new("other", &ctx);
+    let foo = other.add_function("foo", sig);
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(foo.append("entry"));
+    builder.build_ret_void();
+    module.link_destroy(other).unwrap();
+    assert!(!module.get_function("foo").unwrap().is_declaration());
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+fn test_delete_function() {
+    let ctx = Context::new();
+    let module = Module::new("delete", &ctx);
+    module.add_function("survivor", Type::get::<fn() -> ()>(&ctx));
+    let doomed = module.add_function("doomed", Type::get::<fn() -> ()>(&ctx));
+    unsafe { doomed.delete() };
+    let names: Vec<_> = module.functions().map(|func| func.get_name().unwrap()).collect();
+    assert_eq!(names, ["survivor"]);
+    assert!(module.get_function("doomed").is_none());
+}
+
+#[test]
+fn test_is_declaration() {
+    let ctx = Context::new();
+    let module = Module::new("declarations", &ctx);
+    let func = module.add_function("imported", Type::get::<fn() -> ()>(&ctx));
+    assert!(func.is_declaration());
+    assert!(func.get_entry().is_none());
+    func.append("entry");
+    assert!(!func.is_declaration());
+    assert!(func.get_entry().is_some());
+}
+
+#[test]
+fn test_intrinsic_id() {
+    let ctx = Context::new();
+    let module = Module::new("intrinsics", &ctx);
+    let sqrt = module.add_function("llvm.sqrt.f64", Type::get::<fn(f64) -> f64>(&ctx));
+    assert!(sqrt.get_intrinsic_id().unwrap() != 0);
+    let func = module.add_function("sqrt", Type::get::<fn(f64) -> f64>(&ctx));
+    assert_eq!(func.get_intrinsic_id(), None);
+}
+
+#[test]
+fn test_intrinsic_declaration() {
+    let ctx = Context::new();
+    let module = Module::new("intrinsics", &ctx);
+    let (double, sig) = (Type::get::<f64>(&ctx), Type::get::<fn(f64) -> f64>(&ctx));
+    let sqrt = module.get_intrinsic_declaration("llvm.sqrt", &[double], sig).unwrap();
+    assert_eq!(sqrt.get_name(), Some("llvm.sqrt.f64"));
+    assert!(sqrt.get_intrinsic_id().unwrap() != 0);
+    assert!(module.get_intrinsic_declaration("llvm.sqrt", &[double], sig).unwrap() == sqrt);
+    assert!(module.get_intrinsic_declaration("llvm.nonsense", &[double], sig).is_none());
+    assert!(module.get_function("llvm.nonsense.f64").is_none());
+}
+
+#[test]
+fn test_named_metadata() {
+    let ctx = Context::new();
+    let module = Module::new("metadata", &ctx);
+    assert_eq!(module.get_named_metadata_operands("my.notes").len(), 0);
+    let first = Value::new_metadata_node(&ctx, &[Value::new_metadata_string(&ctx, "first")]);
+    let second = Value::new_metadata_node(&ctx, &[Value::new_metadata_string(&ctx, "second")]);
+    module.add_named_metadata_operand("my.notes", first);
+    module.add_named_metadata_operand("my.notes", second);
+    let nodes = module.get_named_metadata_operands("my.notes");
+    assert_eq!(nodes.len(), 2);
+    assert!(nodes[0] == first && nodes[1] == second);
+    assert!(format!("{:?}", module).contains("!my.notes = !{!0, !1}"));
+    assert!(module.try_add_named_metadata_operand("my\0notes", first).is_err());
+    assert!(module.try_get_named_metadata_operands("my\0notes").is_err());
+    assert_eq!(module.try_get_named_metadata_operands("my.notes").unwrap().len(), 2);
+}
+
+#[test]
+fn test_try_new() {
+    let ctx = Context::new();
+    assert!(Module::try_new("bad\0name", &ctx).is_err());
+    let module = Module::try_new("good", &ctx).unwrap();
+    assert!(module.get_function("bad\0name").is_none());
+    assert!(module.get_type("bad\0name").is_none());
+}
+
+#[test]
+fn test_try_add_function() {
+    let ctx = Context::new();
+    let module = Module::new("functions", &ctx);
+    let sig = Type::get::<fn() -> ()>(&ctx);
+    assert!(module.try_add_function("bad\0name", sig).is_err());
+    let func = module.try_add_function("good", sig).unwrap();
+    assert_eq!(func.get_name(), Some("good"));
+    assert!(func.try_set_name("bad\0name").is_err());
+    assert_eq!(func.get_name(), Some("good"));
+}
+
+#[test]
+fn test_clone() {
+    let ctx = Context::new();
+    let module = Module::new("original", &ctx);
+    module.add_function("kept", Type::get::<fn() -> ()>(&ctx));
+    module.add_function("removed", Type::get::<fn() -> ()>(&ctx));
+    module.add_global("counter", Type::get::<i32>(&ctx));
+    let copy = module.clone();
+    assert!(copy.get_global("counter").is_some());
+    unsafe { copy.get_function("removed").unwrap().delete() };
+    assert!(copy.get_function("removed").is_none());
+    assert!(copy.get_function("kept").is_some());
+    let names: Vec<_> = module.functions().map(|func| func.get_name().unwrap()).collect();
+    assert_eq!(names, ["kept", "removed"]);
+}
+
+#[test]
+fn test_global_flags() {
+    let ctx = Context::new();
+    let module = Module::new("globals", &ctx);
+    let message = module.add_global_variable("message", Value::new_string(&ctx, "hello", true));
+    message.set_constant(true);
+    message.set_linkage(Linkage::Private);
+    assert!(!message.has_unnamed_addr());
+    message.set_unnamed_addr(true);
+    assert!(message.has_unnamed_addr());
+    assert!(format!("{:?}", message).contains("@message = private unnamed_addr constant [6 x i8]"));
+    let flag = module.add_global("flag", Type::get::<bool>(&ctx));
+    assert!(!flag.is_externally_initialized());
+    flag.set_externally_initialized(true);
+    assert!(flag.is_externally_initialized());
+    assert!(format!("{:?}", flag).contains("externally_initialized"));
+}
@@ -0,0 +1,49 @@
+extern crate llvm;
+use llvm::*;
+
+#[test]
+fn test_pass_manager() {
+    let ctx = Context::new();
+    let module = Module::new("passes", &ctx);
+    let func = module.add_function("answer", Type::get::<fn() -> i32>(&ctx));
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(func.append("entry"));
+    let ptr = builder.build_alloca(Type::get::<i32>(&ctx));
+    builder.build_store(40i32.compile(&ctx), ptr);
+    let value = builder.build_load(ptr);
+    builder.build_ret(builder.build_add(value, 2i32.compile(&ctx)));
+    assert!(format!("{:?}", func).contains("alloca"));
+    let passes = PassManager::new();
+    passes.add_promote_memory_to_register();
+    passes.add_instruction_combining();
+    passes.add_cfg_simplification();
+    passes.add_gvn();
+    assert!(passes.run(&module));
+    let text = format!("{:?}", module.get_function("answer").unwrap());
+    assert!(!text.contains("alloca"));
+    assert!(text.contains("ret i32 42"));
+}
+
+#[test]
+fn test_pass_manager_builder() {
+    let ctx = Context::new();
+    let module = Module::new("inlining", &ctx);
+    let sig = Type::get::<fn(i32) -> i32>(&ctx);
+    let callee = module.add_function("callee", sig);
+    callee.add_attribute(Attribute::AlwaysInline);
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(callee.append("entry"));
+    builder.build_ret(builder.build_add(&callee[0], 1i32.compile(&ctx)));
+    let caller = module.add_function("caller", sig);
+    builder.position_at_end(caller.append("entry"));
+    builder.build_ret(builder.build_call(callee, &[&caller[0]]));
+    let passes = PassManager::new();
+    let pipeline = PassManagerBuilder::new();
+    pipeline.set_opt_level(2);
+    pipeline.use_inliner_with_threshold(225);
+    pipeline.populate_module_pass_manager(&passes);
+    passes.run(&module);
+    let text = format!("{:?}", module.get_function("caller").unwrap());
+    assert!(!text.contains("call"));
+    assert!(text.contains("add i32 %0, 1"));
+}
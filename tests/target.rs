@@ -0,0 +1,58 @@
+extern crate llvm;
+use llvm::*;
+
+const X86_64_LAYOUT: &'static str = "e-m:e-i64:64-f80:128-n8:16:32:64-S128";
+
+#[test]
+fn test_type_layout() {
+    let ctx = Context::new();
+    let target = TargetData::new(X86_64_LAYOUT);
+    let i64_t = Type::get_i64(&ctx);
+    assert_eq!(i64_t.size_in_bits(&target), 64);
+    assert_eq!(i64_t.abi_alignment(&target), 8);
+    assert_eq!(i64_t.get_size(&target), 8);
+    assert!(TargetData::try_new("e-m:e\0").is_err());
+}
+
+#[test]
+fn test_emit_object_file() {
+    initialize_native_target().unwrap();
+    let ctx = Context::new();
+    let module = Module::new("object", &ctx);
+    let func = module.add_function("answer", Type::get::<fn() -> i32>(&ctx));
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(func.append("entry"));
+    builder.build_ret(42i32.compile(&ctx));
+    let triple = get_default_target_triple();
+    let target = Target::from_triple(triple).unwrap();
+    let machine = TargetMachine::new(target, triple, "", "", CodeGenOptLevel::Default, RelocMode::Default, CodeModel::Default);
+    assert!(machine.get_target() == target);
+    assert!(TargetMachine::try_new(target, triple, "bad\0cpu", "", CodeGenOptLevel::Default, RelocMode::Default, CodeModel::Default).is_err());
+    let path = std::env::temp_dir().join(format!("llvm_rs_test_emit_object_file_{}.o", std::process::id()));
+    machine.emit_to_file(&module, &path, FileType::Object).unwrap();
+    let len = std::fs::metadata(&path).unwrap().len();
+    std::fs::remove_file(&path).unwrap();
+    assert!(len > 0);
+}
+
+#[test]
+fn test_initialize_native_target() {
+    initialize_native_target().unwrap();
+    initialize_native_target().unwrap();
+    assert!(Target::from_triple(get_default_target_triple()).is_ok());
+    initialize_all_targets();
+}
+
+#[test]
+fn test_struct_element_offsets() {
+    let ctx = Context::new();
+    let target = TargetData::new(X86_64_LAYOUT);
+    let ty = StructType::new(&ctx, &[Type::get_i8(&ctx), Type::get_i32(&ctx)], false);
+    assert_eq!(ty.element_offset(&target, 0), 0);
+    assert_eq!(ty.element_offset(&target, 1), 4);
+    assert_eq!(ty.element_at_offset(&target, 0), 0);
+    assert_eq!(ty.element_at_offset(&target, 2), 0);
+    assert_eq!(ty.element_at_offset(&target, 5), 1);
+    let packed = StructType::new(&ctx, &[Type::get_i8(&ctx), Type::get_i32(&ctx)], true);
+    assert_eq!(packed.element_offset(&target, 1), 1);
+}
@@ -0,0 +1,134 @@
+extern crate llvm;
+use llvm::*;
+
+#[test]
+fn test_int_types() {
+    let ctx = Context::new();
+    assert_eq!(Type::get_int(&ctx, 7).get_int_width(), Some(7));
+    assert_eq!(Type::get_int(&ctx, 128).get_int_width(), Some(128));
+    assert_eq!(Type::get_i1(&ctx).get_int_width(), Some(1));
+    assert_eq!(Type::get_i8(&ctx).get_int_width(), Some(8));
+    assert_eq!(Type::get_i16(&ctx).get_int_width(), Some(16));
+    assert_eq!(Type::get_i32(&ctx).get_int_width(), Some(32));
+    assert_eq!(Type::get_i64(&ctx), Type::get::<i64>(&ctx));
+    assert_eq!(Type::get::<f64>(&ctx).get_int_width(), None);
+}
+
+#[test]
+fn test_float_types() {
+    let ctx = Context::new();
+    assert_eq!(format!("{:?}", Type::get_double(&ctx)), "double");
+    assert_eq!(format!("{:?}", Type::get_float(&ctx)), "float");
+    assert_eq!(format!("{:?}", Type::get_half(&ctx)), "half");
+    assert_eq!(format!("{:?}", Type::get_fp128(&ctx)), "fp128");
+    assert!(Type::get_void(&ctx).is_void());
+    assert!(Type::get_fp128(&ctx).is_floating_point());
+    assert!(Type::get_double(&ctx).is_floating_point());
+    assert!(!Type::get_i32(&ctx).is_floating_point());
+}
+
+#[test]
+fn test_pointer_types() {
+    let ctx = Context::new();
+    let i32_t = Type::get_i32(&ctx);
+    let ptr = Type::get_pointer(i32_t, 1);
+    assert_eq!(format!("{:?}", ptr), "i32 addrspace(1)*");
+    assert_eq!(PointerType::from_super(ptr).unwrap().get_address_space(), 1);
+    let ptr = i32_t.pointer_to();
+    assert_eq!(ptr, PointerType::new(i32_t));
+    assert_eq!(PointerType::from_super(ptr).unwrap().get_address_space(), 0);
+}
+
+#[test]
+fn test_array_types() {
+    let ctx = Context::new();
+    let i32_t = Type::get_i32(&ctx);
+    let array = Type::get_array(i32_t, 4);
+    assert_eq!(format!("{:?}", array), "[4 x i32]");
+    assert_eq!(array.get_element_type(), Some(i32_t));
+    assert_eq!(array.get_length(), Some(4));
+    assert_eq!(Type::get_array(i32_t, 0).get_length(), Some(0));
+    assert_eq!(i32_t.get_length(), None);
+}
+
+#[test]
+fn test_vector_types() {
+    let ctx = Context::new();
+    let float_t = Type::get_float(&ctx);
+    let vector = Type::get_vector(float_t, 8);
+    assert_eq!(format!("{:?}", vector), "<8 x float>");
+    assert_eq!(vector.get_element_type(), Some(float_t));
+    assert_eq!(vector.get_length(), Some(8));
+}
+
+#[test]
+#[should_panic]
+fn test_empty_vector_type() {
+    let ctx = Context::new();
+    Type::get_vector(Type::get_float(&ctx), 0);
+}
+
+#[test]
+fn test_recursive_struct() {
+    let ctx = Context::new();
+    let node = StructType::new_opaque(&ctx, "node");
+    assert!(node.is_opaque());
+    assert_eq!(node.get_name(), Some("node"));
+    let i32_t = Type::get_i32(&ctx);
+    node.set_body(&[i32_t, node.pointer_to()], false);
+    assert!(!node.is_opaque());
+    assert_eq!(node.get_elements(), vec![i32_t, PointerType::new(node)]);
+    assert_eq!(StructType::new(&ctx, &[i32_t], false).get_name(), None);
+}
+
+#[test]
+fn test_var_arg_function_type() {
+    let ctx = Context::new();
+    let i32_t = Type::get_i32(&ctx);
+    let str_t = Type::get_i8(&ctx).pointer_to();
+    let printf = FunctionType::new(i32_t, &[str_t], true);
+    assert!(printf.is_var_arg());
+    assert_eq!(format!("{:?}", printf), "i32 (i8*, ...)");
+    assert_eq!(printf.get_return(), i32_t);
+    assert_eq!(printf.get_params(), vec![str_t]);
+    assert!(!FunctionType::new(i32_t, &[str_t], false).is_var_arg());
+}
+
+#[test]
+fn test_type_equality() {
+    let ctx = Context::new();
+    assert_eq!(Type::get_i32(&ctx), Type::get_i32(&ctx));
+    assert!(Type::get_i32(&ctx) != Type::get_i64(&ctx));
+    assert_eq!(Type::get_i32(&ctx).get_kind(), TypeKind::Integer);
+    assert_eq!(Type::get_double(&ctx).get_kind(), TypeKind::Double);
+    assert_eq!(Type::get_void(&ctx).get_kind(), TypeKind::Void);
+    assert_eq!(Type::get_i32(&ctx).pointer_to().get_kind(), TypeKind::Pointer);
+    assert_eq!(Type::get::<fn() -> ()>(&ctx).get_kind(), TypeKind::Function);
+}
+
+#[test]
+fn test_inline_asm() {
+    let ctx = Context::new();
+    let module = Module::new("asm", &ctx);
+    let func = module.add_function("barrier", Type::get::<fn() -> ()>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let sig = FunctionType::new(Type::get_void(&ctx), &[], false);
+    let asm = sig.inline_asm("", "~{memory}", true, false);
+    builder.build_value_call(asm, &[]);
+    builder.build_ret_void();
+    module.verify().unwrap();
+    assert!(format!("{:?}", func).contains("call void asm sideeffect \"\", \"~{memory}\"()"));
+}
+
+#[test]
+fn test_type_debug() {
+    let ctx = Context::new();
+    let i32_t = Type::get_i32(&ctx);
+    assert_eq!(format!("{:?}", i32_t), "i32");
+    assert_eq!(format!("{:?}", i32_t.pointer_to()), "i32*");
+    let pair = StructType::new(&ctx, &[i32_t, Type::get_double(&ctx)], false);
+    assert_eq!(format!("{:?}", pair), "{ i32, double }");
+    assert_eq!(format!("{:?}", Type::get::<fn(i8) -> f32>(&ctx)), "float (i8)");
+}
@@ -0,0 +1,249 @@
+extern crate llvm;
+use llvm::*;
+
+#[test]
+fn test_uses() {
+    let ctx = Context::new();
+    let module = Module::new("uses", &ctx);
+    let func = module.add_function("double", Type::get::<fn(i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let x = &func[0];
+    let sum = builder.build_add(x, x);
+    builder.build_ret(sum);
+    assert_eq!(x.uses().count(), 2);
+    for use_ in x.uses() {
+        assert!(use_.get_user() == sum);
+        assert!(use_.get_used_value() == &**x);
+    }
+}
+
+#[test]
+fn test_replace_all_uses_with() {
+    let ctx = Context::new();
+    let module = Module::new("rauw", &ctx);
+    let func = module.add_function("pick", Type::get::<fn(i32, i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let (a, b) = (&func[0], &func[1]);
+    let sum = builder.build_add(a, a);
+    builder.build_ret(sum);
+    a.replace_all_uses_with(b);
+    assert_eq!(a.uses().count(), 0);
+    assert_eq!(b.uses().count(), 2);
+}
+
+#[test]
+fn test_call_conv() {
+    let ctx = Context::new();
+    let module = Module::new("call_conv", &ctx);
+    let callee = module.add_function("callee", Type::get::<fn(i32) -> i32>(&ctx));
+    assert_eq!(callee.get_call_conv(), CallConv::C);
+    callee.set_call_conv(CallConv::Fast);
+    assert_eq!(callee.get_call_conv(), CallConv::Fast);
+    let func = module.add_function("caller", Type::get::<fn(i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let result = builder.build_call(callee, &[&func[0]]);
+    let call = Call::from_super(result).unwrap();
+    assert!(call.get_called_value() == &***callee);
+    call.set_call_conv(CallConv::Fast);
+    assert_eq!(call.get_call_conv(), CallConv::Fast);
+    builder.build_ret(result);
+    assert!(format!("{:?}", module).contains("call fastcc i32 @callee"));
+}
+
+#[test]
+fn test_other_call_conv() {
+    let ctx = Context::new();
+    let module = Module::parse_ir(&ctx, "define ghccc void @ghc() {\n  ret void\n}\n").unwrap();
+    let func = module.get_function("ghc").unwrap();
+    assert_eq!(func.get_call_conv(), CallConv::Other(10));
+    func.set_call_conv(CallConv::Other(100));
+    assert_eq!(func.get_call_conv(), CallConv::Other(100));
+    func.set_call_conv(CallConv::Fast);
+    assert_eq!(func.get_call_conv(), CallConv::Fast);
+}
+
+#[test]
+fn test_linkage_and_visibility() {
+    let ctx = Context::new();
+    let module = Module::new("linkage", &ctx);
+    let func = module.add_function("helper", Type::get::<fn() -> ()>(&ctx));
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(func.append("entry"));
+    builder.build_ret_void();
+    assert_eq!(func.get_linkage(), Linkage::External);
+    func.set_linkage(Linkage::Internal);
+    assert_eq!(func.get_linkage(), Linkage::Internal);
+    assert!(format!("{:?}", module).contains("define internal void @helper"));
+    let exported = module.add_function("exported", Type::get::<fn() -> ()>(&ctx));
+    assert_eq!(exported.get_visibility(), Visibility::Default);
+    exported.set_visibility(Visibility::Hidden);
+    assert_eq!(exported.get_visibility(), Visibility::Hidden);
+    assert!(format!("{:?}", module).contains("declare hidden void @exported"));
+}
+
+#[test]
+fn test_alignment() {
+    let ctx = Context::new();
+    let module = Module::new("alignment", &ctx);
+    let func = module.add_function("aligned", Type::get::<fn() -> ()>(&ctx));
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(func.append("entry"));
+    let slot = builder.build_alloca(Type::get::<f64>(&ctx));
+    slot.set_alignment(16);
+    assert_eq!(slot.get_alignment(), 16);
+    let load = builder.build_load(slot);
+    load.set_alignment(16);
+    builder.build_ret_void();
+    let text = format!("{:?}", module);
+    assert!(text.contains("alloca double, align 16"));
+    assert_eq!(text.matches("align 16").count(), 2);
+}
+
+#[test]
+#[should_panic(expected = "not a power of two")]
+fn test_alignment_not_power_of_two() {
+    let ctx = Context::new();
+    let module = Module::new("alignment", &ctx);
+    let func = module.add_function("aligned", Type::get::<fn() -> ()>(&ctx));
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(func.append("entry"));
+    builder.build_alloca(Type::get::<f64>(&ctx)).set_alignment(12);
+}
+
+#[test]
+fn test_params() {
+    let ctx = Context::new();
+    let module = Module::new("params", &ctx);
+    let func = module.add_function("mixed", Type::get::<fn(i32, f64, i8) -> ()>(&ctx));
+    assert_eq!(func.get_param_count(), 3);
+    let types: Vec<_> = func.params().map(|param| param.get_type()).collect();
+    assert_eq!(types, [Type::get::<i32>(&ctx), Type::get::<f64>(&ctx), Type::get::<i8>(&ctx)]);
+    for (index, param) in func.params().enumerate() {
+        assert!(param == &func[index]);
+    }
+    let empty = module.add_function("empty", Type::get::<fn() -> ()>(&ctx));
+    assert_eq!(empty.params().count(), 0);
+}
+
+#[test]
+fn test_section_and_gc() {
+    let ctx = Context::new();
+    let module = Module::new("sections", &ctx);
+    let func = module.add_function("hot", Type::get::<fn() -> ()>(&ctx));
+    assert_eq!(func.get_section(), None);
+    func.set_section(".fast_text");
+    assert_eq!(func.get_section(), Some(".fast_text"));
+    assert!(format!("{:?}", module).contains("section \".fast_text\""));
+    func.set_section("");
+    assert_eq!(func.get_section(), None);
+    assert_eq!(func.get_gc(), None);
+    func.set_gc("shadow-stack");
+    assert_eq!(func.get_gc(), Some("shadow-stack"));
+}
+
+#[test]
+fn test_get_param() {
+    let ctx = Context::new();
+    let module = Module::new("params", &ctx);
+    let func = module.add_function("pair", Type::get::<fn(i32, f64) -> ()>(&ctx));
+    assert!(func.get_param(0).unwrap() == &func[0]);
+    assert!(func.get_param(1).unwrap().get_type() == Type::get::<f64>(&ctx));
+    assert!(func.get_param(2).is_none());
+    assert!(func.get_param(usize::max_value()).is_none());
+}
+
+#[test]
+fn test_metadata() {
+    let ctx = Context::new();
+    let module = Module::new("metadata", &ctx);
+    let func = module.add_function("tagged", Type::get::<fn(i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let sum = builder.build_add(&func[0], &func[0]);
+    builder.build_ret(sum);
+    let kind = ctx.get_metadata_kind_id("my.annotation");
+    assert_eq!(ctx.get_metadata_kind_id("my.annotation"), kind);
+    assert!(sum.get_metadata(kind).is_none());
+    let tag = Value::new_metadata_string(&ctx, "hot");
+    let node = Value::new_metadata_node(&ctx, &[tag]);
+    sum.set_metadata(kind, node);
+    assert!(sum.get_metadata(kind) == Some(node));
+    let text = format!("{:?}", module);
+    assert!(text.contains("!my.annotation !0"));
+    assert!(text.contains("!0 = !{!\"hot\"}"));
+}
+
+#[test]
+fn test_operands() {
+    let ctx = Context::new();
+    let module = Module::new("operands", &ctx);
+    let func = module.add_function("add", Type::get::<fn(i32, i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let sum = builder.build_add(&func[0], &func[1]);
+    builder.build_ret(sum);
+    assert_eq!(sum.get_num_operands(), 2);
+    assert!(sum.get_operand(0) == Some(&*func[0]));
+    assert!(sum.get_operand(1) == Some(&*func[1]));
+    assert!(sum.get_operand(2).is_none());
+    sum.set_operand(1, &func[0]);
+    assert!(sum.get_operand(1) == Some(&*func[0]));
+    assert!(format!("{:?}", func).contains("add i32 %0, %0"));
+}
+
+#[test]
+fn test_instruction_opcode() {
+    let ctx = Context::new();
+    let module = Module::new("opcodes", &ctx);
+    let func = module.add_function("deref", Type::get::<fn(*const i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let load = builder.build_load(&func[0]);
+    let ret = builder.build_ret(load);
+    assert_eq!(load.get_instruction_opcode(), Some(Opcode::Load));
+    assert_eq!(ret.get_instruction_opcode(), Some(Opcode::Ret));
+    assert_eq!(1i32.compile(&ctx).get_instruction_opcode(), None);
+    assert_eq!(func[0].get_instruction_opcode(), None);
+}
+
+#[test]
+fn test_verify_function() {
+    let ctx = Context::new();
+    let module = Module::new("verify", &ctx);
+    let good = module.add_function("good", Type::get::<fn() -> i32>(&ctx));
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(good.append("entry"));
+    builder.build_ret(1i32.compile(&ctx));
+    assert!(good.verify().is_ok());
+    let bad = module.add_function("bad", Type::get::<fn() -> i32>(&ctx));
+    builder.position_at_end(bad.append("entry"));
+    builder.build_ret(1.5f64.compile(&ctx));
+    let error = bad.verify().unwrap_err();
+    assert!(error.contains("bad"));
+}
+
+#[test]
+fn test_value_kind() {
+    let ctx = Context::new();
+    let module = Module::new("kinds", &ctx);
+    let func = module.add_function("negate", Type::get::<fn(i32) -> i32>(&ctx));
+    let entry = func.append("entry");
+    let builder = Builder::new(&ctx);
+    builder.position_at_end(entry);
+    let negated = builder.build_neg(&func[0]);
+    builder.build_ret(negated);
+    assert_eq!(func.get_kind(), ValueKind::Function);
+    assert_eq!(func[0].get_kind(), ValueKind::Argument);
+    assert_eq!(negated.get_kind(), ValueKind::Instruction);
+    assert_eq!(1i32.compile(&ctx).get_kind(), ValueKind::ConstantInt);
+    assert_eq!(module.add_global("global", Type::get::<i32>(&ctx)).get_kind(), ValueKind::GlobalVariable);
+}